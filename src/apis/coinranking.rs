@@ -1,5 +1,8 @@
 use serde::Deserialize;
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Deserialize)]
 struct Response {
     data: Data,
@@ -16,10 +19,10 @@ pub struct Coin {
     pub price: String,
 }
 
-pub async fn coins(client: &reqwest::Client) -> reqwest::Result<Vec<Coin>> {
+pub async fn coins(client: &reqwest::Client) -> Result<Vec<Coin>, RequestError> {
     let response = client
-        .get("https://api.coinranking.com/v2/coins?referenceCurrencyUuid=5k-_VTxqtCEI&limit=100")
-        .send()
+        .get(Endpoint::Coinranking.url("/coins?referenceCurrencyUuid=5k-_VTxqtCEI&limit=100"))
+        .send_retrying(Endpoint::Coinranking)
         .await?;
     let result = response.json::<Response>().await?;
 
@@ -6,6 +6,9 @@ use md5::Digest;
 use reqwest::header::{CONTENT_TYPE, ORIGIN};
 use serde::{Deserialize, Serialize};
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct InputData {
@@ -39,7 +42,7 @@ impl fmt::Display for ProcessingError {
 pub async fn process(
     http_client: reqwest::Client,
     image: &[u8],
-) -> reqwest::Result<Result<Media, ProcessingError>> {
+) -> Result<Result<Media, ProcessingError>, RequestError> {
     let json = serde_json::ser::to_string(&InputData {
         busi_id: "different_dimension_me_img_entry",
         images: vec![STANDARD.encode(image)],
@@ -50,13 +53,16 @@ pub async fn process(
 
     loop {
         let result = http_client
-            .post("https://ai.tu.qq.com/trpc.shadow_cv.ai_processor_cgi.AIProcessorCgi/Process")
+            .post(
+                Endpoint::DifferentDimensionMe
+                    .url("/trpc.shadow_cv.ai_processor_cgi.AIProcessorCgi/Process"),
+            )
             .body(json.clone())
             .header(CONTENT_TYPE, "application/json")
             .header(ORIGIN, "https://h5.tu.qq.com")
             .header("x-sign-value", &signature)
             .header("x-sign-version", "v1")
-            .send()
+            .send_retrying(Endpoint::DifferentDimensionMe)
             .await?
             .json::<ProcessingResult>()
             .await?;
@@ -1,5 +1,8 @@
 use serde::Deserialize;
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Deserialize)]
 struct Cube<T> {
     #[serde(rename = "Cube")]
@@ -14,9 +17,11 @@ pub struct Rate {
     pub rate: String,
 }
 
-pub async fn daily(client: &reqwest::Client) -> reqwest::Result<Vec<Rate>> {
-    let response =
-        client.get("https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml").send().await?;
+pub async fn daily(client: &reqwest::Client) -> Result<Vec<Rate>, RequestError> {
+    let response = client
+        .get(Endpoint::Eurofxref.url("/eurofxref-daily.xml"))
+        .send_retrying(Endpoint::Eurofxref)
+        .await?;
 
     let result =
         serde_xml_rs::from_str::<Cube<Cube<Cube<Vec<Rate>>>>>(&response.text().await.unwrap())
@@ -24,3 +29,26 @@ pub async fn daily(client: &reqwest::Client) -> reqwest::Result<Vec<Rate>> {
 
     Ok(result.cube.cube.cube)
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_daily() {
+        FixtureServer::start(
+            Endpoint::Eurofxref,
+            StatusCode::OK,
+            "text/xml",
+            include_str!("fixtures/eurofxref_daily.xml"),
+        )
+        .await;
+
+        let rates = daily(&test_fixtures::http_client()).await.unwrap();
+        let rates = rates.iter().map(|rate| (&*rate.currency, &*rate.rate)).collect::<Vec<_>>();
+        assert_eq!(rates, [("USD", "1.1650"), ("JPY", "175.43"), ("PLN", "4.2461")]);
+    }
+}
@@ -1,11 +1,12 @@
-use std::env;
-
 use reqwest::StatusCode;
 use reqwest::header::AUTHORIZATION;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
+use crate::utilities::settings;
 
 #[derive(Serialize)]
 struct Payload<'a> {
@@ -44,10 +45,10 @@ pub async fn generate(
     prompt: &str,
 ) -> Result<Response, CommandError> {
     let response = http_client
-        .post(format!("https://fal.run/{model}"))
-        .header(AUTHORIZATION, format!("Key {}", env::var("FAL_API_KEY").unwrap()))
+        .post(Endpoint::Fal.url(&format!("/{model}")))
+        .header(AUTHORIZATION, format!("Key {}", settings::get().fal_api_key))
         .json(&Payload { prompt, enable_safety_checker: false, format: "png" })
-        .send()
+        .send_retrying(Endpoint::Fal)
         .await?
         .server_error()?;
 
@@ -1,16 +1,22 @@
 use reqwest::Url;
 use serde_json::Value;
 
-pub async fn complete(http_client: reqwest::Client, query: &str) -> reqwest::Result<Vec<String>> {
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
+pub async fn complete(
+    http_client: reqwest::Client,
+    query: &str,
+) -> Result<Vec<String>, RequestError> {
     let data = http_client
         .get(
             Url::parse_with_params(
-                "https://google.com/complete/search",
+                &Endpoint::Google.url("/complete/search"),
                 [("q", query), ("client", "chrome")],
             )
             .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::Google)
         .await?
         .error_for_status()?
         .json::<(String, Vec<String>, Value, Value, Value)>()
@@ -18,3 +24,26 @@ pub async fn complete(http_client: reqwest::Client, query: &str) -> reqwest::Res
 
     Ok(data.1)
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_complete() {
+        let server = FixtureServer::start(
+            Endpoint::Google,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/google_complete.json"),
+        )
+        .await;
+
+        let completions = complete(test_fixtures::http_client(), "rust").await.unwrap();
+        assert_eq!(completions, ["rust", "rust lang", "rustup"]);
+        assert_eq!(server.requests()[0].target, "/complete/search?q=rust&client=chrome");
+    }
+}
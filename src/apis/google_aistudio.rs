@@ -1,6 +1,6 @@
 use std::borrow::Cow;
+use std::fmt;
 use std::time::Duration;
-use std::{env, fmt};
 
 use futures_util::StreamExt;
 use reqwest::StatusCode;
@@ -10,9 +10,12 @@ use tokio::sync::mpsc;
 use url::Url;
 
 use crate::commands::CommandError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{self, Backoff, RequestError, SendRetrying};
+use crate::utilities::settings;
 
 pub enum GenerationError {
-    Network(reqwest::Error),
+    Network(RequestError),
     Google(Vec<Error>),
 }
 
@@ -66,12 +69,23 @@ pub enum Details {
     Other,
 }
 
+impl Error {
+    fn retry_info(&self) -> Option<&str> {
+        self.details.iter().find_map(|detail| match detail {
+            Details::RetryInfo { retry_delay } => Some(retry_delay.as_str()),
+            Details::Other => None,
+        })
+    }
+
+    /// the delay from `RetryInfo`, which is given in seconds like `"12.5s"`
+    pub fn retry_delay(&self) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.retry_info()?.strip_suffix('s')?.parse().ok()?).ok()
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(retry_delay) = self.details.iter().find_map(|detail| match detail {
-            Details::RetryInfo { retry_delay } => Some(retry_delay),
-            Details::Other => None,
-        }) {
+        if let Some(retry_delay) = self.retry_info() {
             return write!(f, "i was rate-limited by Google. try again in {retry_delay}.");
         }
 
@@ -89,8 +103,8 @@ pub async fn upload_file(
     let response = http_client
         .post(
             Url::parse_with_params(
-                "https://generativelanguage.googleapis.com/upload/v1beta/files",
-                [("key", env::var("MAKERSUITE_API_KEY").unwrap())],
+                &Endpoint::GoogleAiStudio.url("/upload/v1beta/files"),
+                [("key", &settings::get().makersuite_api_key)],
             )
             .unwrap(),
         )
@@ -99,7 +113,7 @@ pub async fn upload_file(
         .header("X-Goog-Upload-Header-Content-Length", &size.to_string())
         .header("X-Goog-Upload-Header-Content-Type", mime_type)
         .json(&Value::Object(Map::new()))
-        .send()
+        .send_retrying(Endpoint::GoogleAiStudio)
         .await?;
 
     let upload_url = response.headers()["X-Goog-Upload-URL"].to_str().unwrap();
@@ -109,7 +123,7 @@ pub async fn upload_file(
         .header("X-Goog-Upload-Offset", "0")
         .header("X-Goog-Upload-Command", "upload, finalize")
         .body(file)
-        .send()
+        .send_retrying(Endpoint::GoogleAiStudio)
         .await?
         .json::<FileResponse>()
         .await?
@@ -121,12 +135,12 @@ pub async fn upload_file(
         file = http_client
             .get(
                 Url::parse_with_params(
-                    &format!("https://generativelanguage.googleapis.com/v1beta/{}", file.name),
-                    [("key", env::var("MAKERSUITE_API_KEY").unwrap())],
+                    &Endpoint::GoogleAiStudio.url(&format!("/v1beta/{}", file.name)),
+                    [("key", &settings::get().makersuite_api_key)],
                 )
                 .unwrap(),
             )
-            .send()
+            .send_retrying(Endpoint::GoogleAiStudio)
             .await?
             .json::<File>()
             .await?;
@@ -248,15 +262,11 @@ pub async fn stream_generate_content<'a>(
     system_instruction: Option<Content<'a>>,
     max_output_tokens: u16,
 ) {
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{model}:streamGenerateContent"
-    );
+    let url =
+        Endpoint::GoogleAiStudio.url(&format!("/v1beta/models/{model}:streamGenerateContent"));
 
-    let response = http_client
-        .post(
-            Url::parse_with_params(&url, [("key", env::var("MAKERSUITE_API_KEY").unwrap())])
-                .unwrap(),
-        )
+    let request = http_client
+        .post(Url::parse_with_params(&url, [("key", &settings::get().makersuite_api_key)]).unwrap())
         .json(&GenerateContentRequest {
             contents,
             safety_settings: &[
@@ -280,35 +290,61 @@ pub async fn stream_generate_content<'a>(
                 max_output_tokens,
                 thinking_config: ThinkingConfig { thinking_budget: 0 },
             },
-        })
-        .send()
-        .await;
+        });
+
+    let mut backoff = Backoff::default();
 
-    let response = match response {
-        Ok(response) => response,
-        Err(err) => {
-            tx.send(Err(GenerationError::Network(err))).unwrap();
+    // sent by hand instead of with `send_retrying` to respect the delay from
+    // `RetryInfo`
+    let response = loop {
+        if let Err(err) = retry::check_circuit(Endpoint::GoogleAiStudio) {
+            tx.send(Err(GenerationError::Network(err.into()))).unwrap();
             return;
         }
-    };
 
-    let status = response.status();
+        let result = request.try_clone().unwrap().send().await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(err) => {
+                if err.is_connect() && backoff.wait(None).await {
+                    continue;
+                }
+
+                retry::record_error(Endpoint::GoogleAiStudio, &err);
+                tx.send(Err(GenerationError::Network(err.into()))).unwrap();
+                return;
+            }
+        };
 
-    if status != StatusCode::OK {
-        let error_response = response.json::<Vec<ErrorResponse>>().await;
+        let status = response.status();
+
+        if status == StatusCode::OK {
+            retry::record_status(Endpoint::GoogleAiStudio, status);
+            break response;
+        }
 
-        match error_response {
+        let errors = match response.json::<Vec<ErrorResponse>>().await {
             Ok(error_response) => {
-                tx.send(Err(GenerationError::Google(
-                    error_response.into_iter().map(|error| error.error).collect(),
-                )))
-                .unwrap();
+                error_response.into_iter().map(|error| error.error).collect::<Vec<_>>()
+            }
+            Err(err) => {
+                retry::record_status(Endpoint::GoogleAiStudio, status);
+                tx.send(Err(GenerationError::Network(err.into()))).unwrap();
+                return;
             }
-            Err(err) => tx.send(Err(GenerationError::Network(err))).unwrap(),
+        };
+
+        if matches!(status, StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE)
+            && backoff.wait(errors.iter().find_map(Error::retry_delay)).await
+        {
+            continue;
         }
 
+        retry::record_status(Endpoint::GoogleAiStudio, status);
+        tx.send(Err(GenerationError::Google(errors))).unwrap();
         return;
-    }
+    };
 
     let mut buffer = Vec::new();
     let mut stream = response.bytes_stream();
@@ -317,7 +353,7 @@ pub async fn stream_generate_content<'a>(
         let part = match part {
             Ok(part) => part,
             Err(err) => {
-                tx.send(Err(GenerationError::Network(err))).unwrap();
+                tx.send(Err(GenerationError::Network(err.into()))).unwrap();
                 return;
             }
         };
@@ -342,3 +378,21 @@ pub async fn stream_generate_content<'a>(
 
     tx.send(Ok(serde_json::from_str(&String::from_utf8(buffer).unwrap()).unwrap())).unwrap();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let response = serde_json::from_str::<ErrorResponse>(
+            r#"{"error": {"code": 429, "message": "Resource has been exhausted", "details": [
+                {"@type": "type.googleapis.com/google.rpc.Help", "links": []},
+                {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "12.5s"}
+            ]}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.error.retry_delay(), Some(Duration::from_millis(12500)));
+    }
+}
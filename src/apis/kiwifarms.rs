@@ -2,9 +2,11 @@ use std::time::Duration;
 
 use reqwest::StatusCode;
 
+use crate::utilities::endpoints::Endpoint;
+
 pub async fn status(http_client: reqwest::Client) -> reqwest::Result<StatusCode> {
     Ok(http_client
-        .get("https://kiwifarms.st/")
+        .get(Endpoint::KiwiFarms.url("/"))
         .timeout(Duration::from_secs(10))
         .send()
         .await?
@@ -1,6 +1,9 @@
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Serialize)]
 struct Payload {
     pub expr: String,
@@ -19,9 +22,12 @@ struct Error {
 pub async fn evaluate(
     http_client: reqwest::Client,
     expr: String,
-) -> reqwest::Result<Result<String, String>> {
-    let response =
-        http_client.post("https://api.mathjs.org/v4/").json(&Payload { expr }).send().await?;
+) -> Result<Result<String, String>, RequestError> {
+    let response = http_client
+        .post(Endpoint::Mathjs.url("/"))
+        .json(&Payload { expr })
+        .send_retrying(Endpoint::Mathjs)
+        .await?;
 
     let result = match response.status() {
         StatusCode::OK => Ok(response.json::<Response>().await?.result),
@@ -30,3 +36,41 @@ pub async fn evaluate(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_evaluate() {
+        let server = FixtureServer::start(
+            Endpoint::Mathjs,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/mathjs.json"),
+        )
+        .await;
+
+        let result = evaluate(test_fixtures::http_client(), "2 + 2".into()).await.unwrap();
+        assert_eq!(result, Ok("4".into()));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].target, "/");
+        assert_eq!(requests[0].body, r#"{"expr":"2 + 2"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_error() {
+        FixtureServer::start(
+            Endpoint::Mathjs,
+            StatusCode::BAD_REQUEST,
+            "application/json",
+            include_str!("fixtures/mathjs_error.json"),
+        )
+        .await;
+
+        let result = evaluate(test_fixtures::http_client(), "x".into()).await.unwrap();
+        assert_eq!(result, Err("Undefined symbol x".into()));
+    }
+}
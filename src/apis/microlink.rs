@@ -3,6 +3,8 @@ use serde::Deserialize;
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
 
 #[derive(Deserialize)]
 struct Response {
@@ -34,7 +36,7 @@ pub async fn screenshot(
     let response = http_client
         .get(
             Url::parse_with_params(
-                "https://api.microlink.io/",
+                &Endpoint::Microlink.url("/"),
                 [
                     ("url", url.as_str()),
                     ("color_scheme", "dark"),
@@ -50,7 +52,7 @@ pub async fn screenshot(
             )
             .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::Microlink)
         .await?
         .server_error()?;
 
@@ -2,6 +2,8 @@ use serde::Deserialize;
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
 
 #[derive(Deserialize)]
 pub struct Joke {
@@ -11,8 +13,8 @@ pub struct Joke {
 
 pub async fn joke(http_client: reqwest::Client) -> Result<Joke, CommandError> {
     let joke = http_client
-        .get("https://qdpnjkjlql.execute-api.eu-central-1.amazonaws.com/json")
-        .send()
+        .get(Endpoint::Moveit.url("/json"))
+        .send_retrying(Endpoint::Moveit)
         .await?
         .server_error()?
         .json::<Joke>()
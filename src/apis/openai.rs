@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
 
 #[derive(Serialize)]
 struct Request<'a> {
@@ -65,17 +67,17 @@ impl fmt::Display for ErrorCode {
 
 pub async fn chat_completion(
     http_client: reqwest::Client,
-    base_url: &str,
+    endpoint: Endpoint,
     api_key: &str,
     model: &'static str,
     max_tokens: u16,
     messages: &[Message<'_>],
 ) -> Result<Result<ChatCompletion, Error>, CommandError> {
     let response = http_client
-        .post(format!("{base_url}/chat/completions"))
+        .post(endpoint.url("/chat/completions"))
         .bearer_auth(api_key)
         .json(&Request { messages, model, max_tokens })
-        .send()
+        .send_retrying(endpoint)
         .await?
         .server_error()?;
 
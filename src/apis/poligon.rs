@@ -2,6 +2,8 @@ use serde::Deserialize;
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
 
 #[derive(Deserialize)]
 struct StartitJoke {
@@ -10,8 +12,8 @@ struct StartitJoke {
 
 pub async fn startit_joke(http_client: reqwest::Client) -> Result<String, CommandError> {
     let joke = http_client
-        .get("https://astolfo.poligon.lgbt/api/startit")
-        .send()
+        .get(Endpoint::Poligon.url("/startit"))
+        .send_retrying(Endpoint::Poligon)
         .await?
         .server_error()?
         .json::<StartitJoke>()
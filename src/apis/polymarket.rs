@@ -2,6 +2,9 @@ use serde::Deserialize;
 use time::OffsetDateTime;
 use url::Url;
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Deserialize)]
 #[serde(untagged)]
 enum Response {
@@ -12,7 +15,7 @@ enum Response {
     },
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Event {
     pub slug: String,
@@ -22,7 +25,7 @@ pub struct Event {
     pub markets: Vec<Market>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Market {
     pub outcomes: Vec<String>,
@@ -33,13 +36,13 @@ pub struct Market {
 pub async fn search_events(
     http_client: &reqwest::Client,
     query: &str,
-) -> reqwest::Result<Option<Vec<Event>>> {
+) -> Result<Option<Vec<Event>>, RequestError> {
     let response = http_client
         .get(
-            Url::parse_with_params("https://polymarket.com/api/events/global", [("q", query)])
+            Url::parse_with_params(&Endpoint::Polymarket.url("/events/global"), [("q", query)])
                 .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::Polymarket)
         .await?;
 
     let response = response.json::<Response>().await?;
@@ -49,3 +52,43 @@ pub async fn search_events(
         Response::Ok { events } => Ok(Some(events)),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_search_events() {
+        let server = FixtureServer::start(
+            Endpoint::Polymarket,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/polymarket.json"),
+        )
+        .await;
+
+        let events = search_events(&test_fixtures::http_client(), "fed").await.unwrap().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].slug, "fed-decision-in-december");
+        assert_eq!(events[0].markets[0].outcome_prices, ["0.785", "0.215"]);
+        assert_eq!(events[0].markets[1].group_item_title.as_deref(), Some("No change"));
+        assert_eq!(server.requests()[0].target, "/events/global?q=fed");
+    }
+
+    #[tokio::test]
+    async fn test_search_events_not_found() {
+        FixtureServer::start(
+            Endpoint::Polymarket,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/polymarket_not_found.json"),
+        )
+        .await;
+
+        let events = search_events(&test_fixtures::http_client(), "asdf").await.unwrap();
+        assert!(events.is_none());
+    }
+}
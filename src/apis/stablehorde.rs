@@ -1,4 +1,3 @@
-use std::env;
 use std::time::Duration;
 
 use reqwest::{RequestBuilder, StatusCode, Url};
@@ -7,6 +6,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::commands::CommandError;
 use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{self, SendRetrying};
+use crate::utilities::settings;
 
 trait StableHordeClientAgent {
     fn client_agent_header(self) -> Self;
@@ -14,7 +16,7 @@ trait StableHordeClientAgent {
 
 impl StableHordeClientAgent for RequestBuilder {
     fn client_agent_header(self) -> Self {
-        self.header("Client-Agent", env::var("STABLEHORDE_CLIENT").unwrap())
+        self.header("Client-Agent", &settings::get().stablehorde_client)
     }
 }
 
@@ -80,7 +82,7 @@ pub async fn generate(
     size: (u32, u32),
 ) -> Result<Result<String, String>, CommandError> {
     let response = http_client
-        .post("https://stablehorde.net/api/v2/generate/async")
+        .post(Endpoint::StableHorde.url("/generate/async"))
         .json(&GenerationInput {
             models: vec![model],
             prompt,
@@ -96,9 +98,9 @@ pub async fn generate(
             r2: true,
             shared: true,
         })
-        .header("apikey", env::var("STABLEHORDE_TOKEN").unwrap())
+        .header("apikey", &settings::get().stablehorde_token)
         .client_agent_header()
-        .send()
+        .send_retrying(Endpoint::StableHorde)
         .await?
         .server_error()?;
 
@@ -119,11 +121,16 @@ async fn generation_info<O: DeserializeOwned>(
     action: &str,
     request_id: &str,
 ) -> Result<Result<O, String>, CommandError> {
-    let url = Url::parse(&format!("https://stablehorde.net/api/v2/generate/{action}/{request_id}"))
+    let url = Url::parse(&Endpoint::StableHorde.url(&format!("/generate/{action}/{request_id}")))
         .unwrap();
     let response = loop {
-        match http_client.get(url.clone()).client_agent_header().send().await {
-            Err(err) if err.is_request() => {
+        match http_client
+            .get(url.clone())
+            .client_agent_header()
+            .send_retrying(Endpoint::StableHorde)
+            .await
+        {
+            Err(retry::RequestError::Reqwest(err)) if err.is_request() => {
                 log::warn!("{err}");
                 tokio::time::sleep(Duration::from_secs(1)).await;
             }
@@ -163,10 +170,10 @@ pub async fn results(
 pub async fn cancel_generation(
     http_client: reqwest::Client,
     request_id: &str,
-) -> reqwest::Result<()> {
+) -> Result<(), retry::RequestError> {
     http_client
-        .delete(format!("https://stablehorde.net/api/v2/generate/status/{request_id}"))
-        .send()
+        .delete(Endpoint::StableHorde.url(&format!("/generate/status/{request_id}")))
+        .send_retrying(Endpoint::StableHorde)
         .await?;
 
     Ok(())
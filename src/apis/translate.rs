@@ -1,6 +1,9 @@
 use reqwest::Url;
 use serde::Deserialize;
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Deserialize)]
 struct Response {
     pub sentences: Vec<Sentence>,
@@ -22,11 +25,11 @@ pub async fn single(
     query: &str,
     source_language: Option<&str>,
     target_language: &str,
-) -> reqwest::Result<Translation> {
+) -> Result<Translation, RequestError> {
     let response = http_client
         .get(
             Url::parse_with_params(
-                "https://translate.google.com/translate_a/single",
+                &Endpoint::Translate.url("/translate_a/single"),
                 [
                     ("client", "gtx"),
                     ("sl", source_language.unwrap_or("auto")),
@@ -38,7 +41,7 @@ pub async fn single(
             )
             .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::Translate)
         .await?
         .error_for_status()?
         .json::<Response>()
@@ -55,7 +58,7 @@ pub async fn multiple(
     queries: &[&str],
     source_language: Option<&str>,
     target_language: &str,
-) -> reqwest::Result<Vec<String>> {
+) -> Result<Vec<String>, RequestError> {
     let mut params = vec![
         ("client", "dict-chrome-ex"),
         ("sl", source_language.unwrap_or("auto")),
@@ -65,8 +68,8 @@ pub async fn multiple(
         params.push(("q", query));
     }
     let response = http_client
-        .get(Url::parse_with_params("https://translate.google.com/translate_a/t", params).unwrap())
-        .send()
+        .get(Url::parse_with_params(&Endpoint::Translate.url("/translate_a/t"), params).unwrap())
+        .send_retrying(Endpoint::Translate)
         .await?
         .error_for_status()?;
 
@@ -82,3 +85,28 @@ pub async fn multiple(
 
     Ok(translations)
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_single() {
+        let server = FixtureServer::start(
+            Endpoint::Translate,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/translate_single.json"),
+        )
+        .await;
+
+        let translation =
+            single(test_fixtures::http_client(), "dzień dobry", None, "en").await.unwrap();
+        assert_eq!(translation.text, "good morning");
+        assert_eq!(translation.source_language, "pl");
+        assert!(server.requests()[0].target.starts_with("/translate_a/single?client=gtx&sl=auto"));
+    }
+}
@@ -5,12 +5,15 @@ use reqwest::{StatusCode, Url};
 use serde::Deserialize;
 use time::OffsetDateTime;
 
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::{RequestError, SendRetrying};
+
 #[derive(Deserialize)]
 struct Response {
     pub list: Vec<Card>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Card {
     pub word: String,
     pub definition: String,
@@ -23,13 +26,13 @@ pub struct Card {
     pub permalink: String,
 }
 
-async fn search(http_client: reqwest::Client, term: &str) -> reqwest::Result<Cow<'_, str>> {
+async fn search(http_client: reqwest::Client, term: &str) -> Result<Cow<'_, str>, RequestError> {
     let response = http_client
         .get(
-            Url::parse_with_params("https://www.urbandictionary.com/define.php", [("term", term)])
+            Url::parse_with_params(&Endpoint::UrbanDictionary.url("/define.php"), [("term", term)])
                 .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::UrbanDictionary)
         .await?
         .error_for_status()?;
 
@@ -52,14 +55,17 @@ async fn search(http_client: reqwest::Client, term: &str) -> reqwest::Result<Cow
     }
 }
 
-pub async fn define(http_client: reqwest::Client, term: &str) -> reqwest::Result<Option<Card>> {
+pub async fn define(
+    http_client: reqwest::Client,
+    term: &str,
+) -> Result<Option<Card>, RequestError> {
     let term = search(http_client.clone(), term).await?;
     let definitions = http_client
         .get(
-            Url::parse_with_params("https://api.urbandictionary.com/v0/define", [("term", term)])
+            Url::parse_with_params(&Endpoint::UrbanDictionaryApi.url("/define"), [("term", term)])
                 .unwrap(),
         )
-        .send()
+        .send_retrying(Endpoint::UrbanDictionaryApi)
         .await?
         .error_for_status()?
         .json::<Response>()
@@ -68,3 +74,30 @@ pub async fn define(http_client: reqwest::Client, term: &str) -> reqwest::Result
 
     Ok(definitions.into_iter().next())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures::{self, FixtureServer};
+
+    #[tokio::test]
+    async fn test_define() {
+        let search_server =
+            FixtureServer::start(Endpoint::UrbanDictionary, StatusCode::OK, "text/html", "").await;
+
+        let api_server = FixtureServer::start(
+            Endpoint::UrbanDictionaryApi,
+            StatusCode::OK,
+            "application/json",
+            include_str!("fixtures/urbandictionary.json"),
+        )
+        .await;
+
+        let card = define(test_fixtures::http_client(), "yeet").await.unwrap().unwrap();
+        assert_eq!(card.word, "yeet");
+        assert_eq!(card.thumbs_up, 14372);
+        assert_eq!(card.written_on.year(), 2016);
+        assert_eq!(search_server.requests()[0].target, "/define.php?term=yeet");
+        assert_eq!(api_server.requests()[0].target, "/define?term=yeet");
+    }
+}
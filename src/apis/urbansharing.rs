@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::commands::CommandError;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::retry::SendRetrying;
 
 const QUERY: &str = "query systemStats($systemId: ID!) {
   systemActiveTripCount(systemId: $systemId) {
@@ -61,13 +63,13 @@ pub async fn system_stats(
     system_id: &'static str,
 ) -> Result<Data, CommandError> {
     let response = http_client
-        .post("https://core.urbansharing.com/public/api/v1/graphql")
+        .post(Endpoint::Urbansharing.url("/graphql"))
         .json(&Request {
             operation_name: "systemStats",
             variables: Variables { system_id },
             query: QUERY,
         })
-        .send()
+        .send_retrying(Endpoint::Urbansharing)
         .await?;
 
     Ok(response.json::<Response>().await?.data)
@@ -1,28 +1,42 @@
-use std::env::{self, consts};
+use std::env::consts;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_signal::{Signal, Signals};
 use futures_util::StreamExt;
 use tdlib::enums::{
-    AuthorizationState, BotCommands, ConnectionState, MessageSender, OptionValue, Update,
+    AuthorizationState, BotCommands, CallbackQueryPayload, ConnectionState, MessageSender,
+    OptionValue, Update,
 };
 use tdlib::functions;
 use tdlib::types::{
-    BotCommand, OptionValueBoolean, OptionValueInteger, OptionValueString,
-    UpdateAuthorizationState, UpdateChatMember, UpdateChatPermissions, UpdateChatTitle,
-    UpdateConnectionState, UpdateMessageSendFailed, UpdateMessageSendSucceeded, UpdateNewChat,
-    UpdateNewInlineQuery, UpdateNewMessage, UpdateOption, UpdateUser,
+    BotCommand, CallbackQueryPayloadData, OptionValueBoolean, OptionValueInteger,
+    OptionValueString, UpdateAuthorizationState, UpdateChatMember, UpdateChatPermissions,
+    UpdateChatTitle, UpdateConnectionState, UpdateMessageContent, UpdateMessageSendFailed,
+    UpdateMessageSendSucceeded, UpdateNewCallbackQuery, UpdateNewChat, UpdateNewInlineQuery,
+    UpdateNewMessage, UpdateOption, UpdateUser,
 };
+use time::OffsetDateTime;
 use tokio::signal;
 use tokio::task::JoinHandle;
 
-use crate::commands::{CommandTrait, calculate_inline, dice_reply};
+use crate::commands::{CommandTrait, InlineCommandTrait, MenuScope, calculate_inline, dice_reply};
 use crate::utilities::bot_state::{BotState, BotStatus};
 use crate::utilities::cache::CompactUser;
-use crate::utilities::command_manager::{CommandInstance, CommandManager};
+use crate::utilities::callback_context::CallbackContext;
+use crate::utilities::command_manager::CommandInstance;
+use crate::utilities::i18n::Language;
+use crate::utilities::inline_command_manager::InlineCommandManager;
+use crate::utilities::inline_query_context::InlineQueryContext;
 use crate::utilities::message_filters::MessageDestination;
-use crate::utilities::{command_dispatcher, markov_chain_manager, message_filters, telegram_utils};
+use crate::utilities::storage::StorageError;
+use crate::utilities::telegram_client::TdlibClient;
+use crate::utilities::{
+    command_dispatcher, markov_chain_manager, message_filters, metrics, settings, telegram_utils,
+};
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_mins(5);
 
 pub type TdError = tdlib::types::Error;
 pub type TdResult<T> = Result<T, TdError>;
@@ -30,20 +44,22 @@ pub type TdResult<T> = Result<T, TdError>;
 pub struct Bot {
     pub client_id: i32,
     my_id: Option<i64>,
-    command_manager: CommandManager,
     state: Arc<BotState>,
+    inline_command_manager: InlineCommandManager,
     tasks: Vec<JoinHandle<()>>,
 }
 
 impl Bot {
-    pub fn new() -> Self {
-        Self {
+    pub fn new() -> Result<Self, StorageError> {
+        let state = Arc::new(BotState::new()?);
+
+        Ok(Self {
             client_id: tdlib::create_client(),
             my_id: None,
-            command_manager: CommandManager::new(),
-            state: Arc::new(BotState::new()),
+            state,
+            inline_command_manager: InlineCommandManager::new(),
             tasks: Vec::new(),
-        }
+        })
     }
 
     pub fn run(&mut self) {
@@ -67,6 +83,36 @@ impl Bot {
             *state.status.lock().unwrap() = BotStatus::WaitingToClose;
         });
 
+        if consts::FAMILY != "windows" {
+            tokio::spawn(async move {
+                let mut signals = Signals::new([Signal::Hup]).unwrap();
+
+                while signals.next().await.is_some() {
+                    log::info!("SIGHUP received, reloading settings");
+
+                    if let Err(err) = settings::reload() {
+                        log::error!("{err}");
+                    }
+                }
+            });
+        }
+
+        if let Some(address) = settings::get().metrics_address.clone() {
+            tokio::spawn(metrics::serve(address, self.state.clone()));
+        }
+
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(AUTOSAVE_INTERVAL);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                let state = state.clone();
+                tokio::task::spawn_blocking(move || state.save()).await.unwrap();
+            }
+        });
+
         let state = self.state.clone();
         tokio::spawn(async move {
             signal::ctrl_c().await.unwrap();
@@ -80,6 +126,7 @@ impl Bot {
                 self.on_update(update);
             }
             self.tasks.retain(|t| !t.is_finished());
+            self.state.metrics.set_task_count(self.tasks.len());
             let state = *self.state.status.lock().unwrap();
             match state {
                 BotStatus::WaitingToClose => {
@@ -98,17 +145,7 @@ impl Bot {
             }
         }
 
-        let result = self.state.config.lock().unwrap().save();
-
-        if let Err(err) = result {
-            log::error!("failed to save bot config: {err}");
-        }
-
-        let result = markov_chain_manager::save(&self.state.markov_chain.lock().unwrap());
-
-        if let Err(err) = result {
-            log::error!("failed to save Markov chain: {err}");
-        }
+        self.state.save();
     }
 
     fn close(&mut self) {
@@ -127,6 +164,7 @@ impl Bot {
         match update {
             Update::AuthorizationState(update) => self.on_authorization_state(&update),
             Update::NewMessage(update) => self.on_new_message(update),
+            Update::MessageContent(update) => self.on_message_content(update),
             Update::MessageSendSucceeded(update) => self.on_message_send_succeeded(update),
             Update::MessageSendFailed(update) => self.on_message_send_failed(update),
             Update::NewChat(update) => self.on_new_chat(update),
@@ -136,6 +174,7 @@ impl Bot {
             Update::Option(update) => self.on_option(update),
             Update::ConnectionState(update) => self.on_connection_state(&update),
             Update::NewInlineQuery(update) => self.on_new_inline_query(update),
+            Update::NewCallbackQuery(update) => self.on_new_callback_query(update),
             Update::ChatMember(update) => self.on_chat_member(update),
             _ => (),
         }
@@ -147,18 +186,19 @@ impl Bot {
         match update.authorization_state {
             AuthorizationState::WaitTdlibParameters => {
                 let client_id = self.client_id;
+                let settings = settings::get();
                 self.run_task(async move {
                     functions::set_tdlib_parameters(
                         false,
                         ".data".into(),
                         String::new(),
-                        env::var("DB_ENCRYPTION_KEY").unwrap(),
+                        settings.db_encryption_key.clone(),
                         true,
                         true,
                         false,
                         false,
-                        env::var("API_ID").unwrap().parse().unwrap(),
-                        env::var("API_HASH").unwrap(),
+                        settings.api_id,
+                        settings.api_hash.clone(),
                         "en-raw".into(),
                         env!("CARGO_PKG_NAME").into(),
                         String::new(),
@@ -188,13 +228,9 @@ impl Bot {
             }
             AuthorizationState::WaitPhoneNumber => {
                 let client_id = self.client_id;
+                let token = settings::get().telegram_token.clone();
                 self.run_task(async move {
-                    functions::check_authentication_bot_token(
-                        env::var("TELEGRAM_TOKEN").unwrap(),
-                        client_id,
-                    )
-                    .await
-                    .unwrap();
+                    functions::check_authentication_bot_token(token, client_id).await.unwrap();
                 });
             }
             AuthorizationState::Closed => *self.state.status.lock().unwrap() = BotStatus::Closed,
@@ -204,10 +240,10 @@ impl Bot {
 
     fn on_ready(&mut self) {
         let client_id = self.client_id;
-        let commands = self.command_manager.public_command_list();
+        let menus = self.state.command_manager.lock().unwrap().command_menus();
         self.run_task(async move {
             functions::get_me(client_id).await.unwrap();
-            Self::sync_commands(commands, client_id).await.unwrap();
+            Self::sync_commands(menus, client_id).await.unwrap();
         });
     }
 
@@ -216,9 +252,9 @@ impl Bot {
             message_filters::message_destination(self, self.state.clone(), update.message)
         {
             match destination {
-                MessageDestination::Command { command, arguments, context } => {
+                MessageDestination::Command { command, arguments, context, admin_only } => {
                     self.run_task(command_dispatcher::dispatch_command(
-                        command, arguments, *context,
+                        command, arguments, *context, admin_only,
                     ));
                 }
                 MessageDestination::Dice { message } => {
@@ -232,10 +268,86 @@ impl Bot {
     }
 
     fn on_new_inline_query(&mut self, update: UpdateNewInlineQuery) {
-        self.run_task(calculate_inline::execute(
-            update,
-            self.state.http_client.clone(),
-            self.client_id,
+        if self.state.config.lock().unwrap().blocked_users.contains(&update.sender_user_id) {
+            return; // ignore blocked users
+        }
+
+        if let Some((command, arguments)) = self.inline_command_manager.get_command(&update.query) {
+            let Some(user) = self.state.cache.lock().unwrap().get_user(update.sender_user_id)
+            else {
+                log::warn!("user {} not found in cache", update.sender_user_id);
+                return;
+            };
+
+            self.run_task(command_dispatcher::dispatch_inline_command(
+                command,
+                arguments,
+                InlineQueryContext {
+                    client: Arc::new(TdlibClient::new(self.client_id)),
+                    query_id: update.id,
+                    user,
+                    bot_state: self.state.clone(),
+                },
+            ));
+
+            return;
+        }
+
+        self.run_task(calculate_inline::execute(update, self.state.clone(), self.client_id));
+    }
+
+    fn on_new_callback_query(&mut self, update: UpdateNewCallbackQuery) {
+        let CallbackQueryPayload::Data(CallbackQueryPayloadData { data }) = update.payload else {
+            return; // ignore game and password callbacks
+        };
+
+        if self.state.config.lock().unwrap().blocked_users.contains(&update.sender_user_id) {
+            return; // ignore blocked users
+        }
+
+        let Some((command_name, payload)) = telegram_utils::parse_callback_data(&data) else {
+            log::warn!("invalid callback data: {data:?}");
+            return;
+        };
+
+        let Some(command) = self.get_command(&command_name) else {
+            return;
+        };
+
+        let admin_only = {
+            let config = self.state.config.lock().unwrap();
+
+            if config.is_command_disabled(update.chat_id, command.name()) {
+                return; // ignore commands disabled in this chat
+            }
+
+            config.is_command_admin_only(update.chat_id, command.name())
+        };
+
+        let Some(user) = self.state.cache.lock().unwrap().get_user(update.sender_user_id) else {
+            log::warn!("user {} not found in cache", update.sender_user_id);
+            return;
+        };
+
+        let Some(chat) = self.state.cache.lock().unwrap().get_chat(update.chat_id) else {
+            log::warn!("chat {} not found in cache", update.chat_id);
+            return;
+        };
+
+        self.run_task(command_dispatcher::dispatch_callback(
+            command,
+            payload,
+            CallbackContext {
+                client_id: self.client_id,
+                client: Arc::new(TdlibClient::new(self.client_id)),
+                query_id: update.id,
+                chat_id: update.chat_id,
+                message_id: update.message_id,
+                chat,
+                user,
+                bot_state: self.state.clone(),
+            },
+            admin_only,
         ));
     }
 
@@ -253,7 +365,31 @@ impl Bot {
         self.state.cache.lock().unwrap().update_chat_member(update);
     }
 
+    fn on_message_content(&mut self, update: UpdateMessageContent) {
+        let Some(mut message) =
+            self.state.command_replies.lock().unwrap().rerun(update.chat_id, update.message_id)
+        else {
+            return; // ignore edits of messages that aren't recent commands
+        };
+
+        message.content = update.new_content;
+        message.edit_date = OffsetDateTime::now_utc().unix_timestamp().try_into().unwrap();
+
+        if let Some(MessageDestination::Command { command, arguments, context, admin_only }) =
+            message_filters::message_destination(self, self.state.clone(), message)
+        {
+            self.run_task(command_dispatcher::dispatch_command(
+                command, arguments, *context, admin_only,
+            ));
+        }
+    }
+
     fn on_message_send_succeeded(&self, update: UpdateMessageSendSucceeded) {
+        self.state.command_replies.lock().unwrap().message_sent(
+            update.message.chat_id,
+            update.old_message_id,
+            update.message.id,
+        );
         self.state.message_queue.message_sent(Ok(update));
     }
 
@@ -300,6 +436,7 @@ impl Bot {
 
     fn on_connection_state(&mut self, update: &UpdateConnectionState) {
         log::info!("connection: {:?}", update.state);
+        self.state.metrics.set_connection_state(update.state.clone());
 
         if update.state == ConnectionState::Ready {
             self.on_ready();
@@ -311,25 +448,61 @@ impl Bot {
     }
 
     pub fn add_command(&mut self, command: impl CommandTrait + Send + Sync + 'static) {
-        self.command_manager.add_command(Box::new(command));
+        self.state.command_manager.lock().unwrap().add_command(Box::new(command));
+    }
+
+    pub fn add_inline_command(&mut self, command: impl InlineCommandTrait + Send + Sync + 'static) {
+        self.inline_command_manager.add_command(command);
+    }
+
+    pub fn duplicate_command_name(&self) -> Option<String> {
+        self.state.command_manager.lock().unwrap().duplicate_name().map(Into::into)
     }
 
     pub fn get_command(&self, name: &str) -> Option<Arc<CommandInstance>> {
-        self.command_manager.get_command(name)
+        self.state.command_manager.lock().unwrap().get_command(name)
     }
 
-    pub async fn sync_commands(commands: Vec<BotCommand>, client_id: i32) -> TdResult<()> {
+    pub async fn sync_commands(
+        menus: Vec<(MenuScope, Language, Vec<BotCommand>)>,
+        client_id: i32,
+    ) -> TdResult<()> {
+        // older versions only set the default menu, which the scoped ones now replace
         let BotCommands::BotCommands(bot_commands) =
             functions::get_commands(None, String::new(), client_id).await?;
 
-        if commands == bot_commands.commands {
-            log::debug!("commands already synced");
-            return Ok(());
+        if !bot_commands.commands.is_empty() {
+            functions::delete_commands(None, String::new(), client_id).await?;
+            log::info!("deleted the default command menu");
         }
 
-        let commands_len = commands.len();
-        functions::set_commands(None, String::new(), commands, client_id).await?;
-        log::info!("synced {commands_len} commands");
+        for (scope, language, commands) in menus {
+            // the English menus are used for languages without their own menus
+            let language_code =
+                if language == Language::English { String::new() } else { language.code().into() };
+
+            let BotCommands::BotCommands(bot_commands) = functions::get_commands(
+                Some(scope.bot_command_scope()),
+                language_code.clone(),
+                client_id,
+            )
+            .await?;
+
+            if commands == bot_commands.commands {
+                log::debug!("{scope:?} commands in {} already synced", language.name());
+                continue;
+            }
+
+            let commands_len = commands.len();
+            functions::set_commands(
+                Some(scope.bot_command_scope()),
+                language_code,
+                commands,
+                client_id,
+            )
+            .await?;
+            log::info!("synced {commands_len} {scope:?} commands in {}", language.name());
+        }
 
         Ok(())
     }
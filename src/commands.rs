@@ -2,20 +2,31 @@ use std::borrow::Cow;
 
 use async_trait::async_trait;
 use reqwest::StatusCode;
+use tdlib::enums::BotCommandScope;
 use tdlib::types::FormattedText;
 
 use crate::apis::google_aistudio::GenerationError;
 use crate::bot::TdError;
 use crate::utilities;
 use crate::utilities::api_utils::ServerError;
+use crate::utilities::callback_context::CallbackContext;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::ConversionError;
 use crate::utilities::file_download::DownloadError;
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::inline_query_context::{InlineArticle, InlineQueryContext};
+use crate::utilities::job_queue::ConcurrencyClass;
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::retry::{RequestError, Unavailable};
+use crate::utilities::settings::Role;
 
+pub mod alias;
 pub mod autocomplete;
 pub mod badtranslate;
+pub mod block;
+pub mod broadcast;
 pub mod calculate_inline;
+pub mod cancel;
 pub mod charinfo;
 pub mod cobalt_download;
 pub mod config;
@@ -26,28 +37,80 @@ pub mod different_dimension_me;
 pub mod fal;
 pub mod gemini;
 pub mod groq;
+pub mod help;
 pub mod kebab;
 pub mod kiwifarms;
+pub mod leave;
 pub mod markov_chain;
 pub mod mevo;
 pub mod moveit_joke;
+pub mod notes;
 pub mod openrouter;
 pub mod petittube;
 pub mod ping;
 pub mod polymarket;
 pub mod radio_poligon;
 pub mod radio_sur;
+pub mod reload;
 pub mod screenshot;
 pub mod sex;
 pub mod stablehorde;
 pub mod start;
 pub mod startit_joke;
+pub mod stats;
 pub mod translate;
 pub mod trollslate;
 pub mod urbandictionary;
 pub mod yt_dlp;
 
 pub type CommandResult = Result<(), CommandError>;
+pub type InlineCommandResult = Result<InlineArticle, CommandError>;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandCategory {
+    Ai,
+    Images,
+    Media,
+    Text,
+    Fun,
+    Bot,
+    Other,
+}
+
+impl CommandCategory {
+    pub fn name(self, language: Language) -> &'static str {
+        match self {
+            Self::Ai => i18n::text(language, "category_ai"),
+            Self::Images => i18n::text(language, "category_images"),
+            Self::Media => i18n::text(language, "category_media"),
+            Self::Text => i18n::text(language, "category_text"),
+            Self::Fun => i18n::text(language, "category_fun"),
+            Self::Bot => i18n::text(language, "category_bot"),
+            Self::Other => i18n::text(language, "category_other"),
+        }
+    }
+}
+
+/// the `/` command menus of the bot
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuScope {
+    PrivateChats,
+    GroupChats,
+    /// replaces the group chat menu for chat admins
+    ChatAdministrators,
+}
+
+impl MenuScope {
+    pub const ALL: [Self; 3] = [Self::PrivateChats, Self::GroupChats, Self::ChatAdministrators];
+
+    pub const fn bot_command_scope(self) -> BotCommandScope {
+        match self {
+            Self::PrivateChats => BotCommandScope::AllPrivateChats,
+            Self::GroupChats => BotCommandScope::AllGroupChats,
+            Self::ChatAdministrators => BotCommandScope::AllChatAdministrators,
+        }
+    }
+}
 
 #[async_trait]
 pub trait CommandTrait {
@@ -57,11 +120,58 @@ pub trait CommandTrait {
         None
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 30)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Other
+    }
+
+    /// menus listing the command if it has a description
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats]
+    }
+
+    /// arguments of the command, like `<prompt>`
+    fn usage(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// names and descriptions of the arguments in `usage`
+    fn arguments(&self) -> &[(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// arguments of example uses of the command
+    fn examples(&self) -> &[&'static str] {
+        &[]
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(3, 30)]
+    }
+
+    fn concurrency_class(&self) -> Option<ConcurrencyClass> {
+        None
+    }
+
+    fn required_role(&self) -> Role {
+        Role::User
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult;
+
+    async fn callback(&self, _: &CallbackContext, _: String) -> CommandResult {
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait InlineCommandTrait {
+    fn inline_prefixes(&self) -> &[&str];
+
+    async fn execute_inline(
+        &self,
+        ctx: &InlineQueryContext,
+        arguments: String,
+    ) -> InlineCommandResult;
 }
 
 #[derive(Debug)]
@@ -71,11 +181,27 @@ pub enum CommandError {
     ArgumentConversion(ConversionError),
     Telegram(TdError),
     Server(StatusCode),
+    Unavailable(Unavailable),
     Reqwest(reqwest::Error),
     SerdeJson(serde_json::Error),
     Download(DownloadError),
 }
 
+impl CommandError {
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Custom(_) | Self::CustomFormattedText(_) => "Custom",
+            Self::ArgumentConversion(_) => "ArgumentConversion",
+            Self::Telegram(_) => "Telegram",
+            Self::Server(_) => "Server",
+            Self::Unavailable(_) => "Unavailable",
+            Self::Reqwest(_) => "Reqwest",
+            Self::SerdeJson(_) => "SerdeJson",
+            Self::Download(_) => "Download",
+        }
+    }
+}
+
 impl From<String> for CommandError {
     fn from(value: String) -> Self {
         Self::Custom(Cow::Owned(value))
@@ -103,7 +229,7 @@ impl From<ConversionError> for CommandError {
 impl From<GenerationError> for CommandError {
     fn from(value: GenerationError) -> Self {
         match value {
-            GenerationError::Network(err) => Self::Reqwest(err),
+            GenerationError::Network(err) => err.into(),
             GenerationError::Google(err) => Self::Custom(Cow::Owned(
                 err.into_iter().map(|error| error.to_string()).collect::<Vec<_>>().join("\n"),
             )),
@@ -129,6 +255,21 @@ impl From<reqwest::Error> for CommandError {
     }
 }
 
+impl From<RequestError> for CommandError {
+    fn from(value: RequestError) -> Self {
+        match value {
+            RequestError::Reqwest(err) => Self::Reqwest(err),
+            RequestError::Unavailable(unavailable) => Self::Unavailable(unavailable),
+        }
+    }
+}
+
+impl From<Unavailable> for CommandError {
+    fn from(value: Unavailable) -> Self {
+        Self::Unavailable(value)
+    }
+}
+
 impl From<serde_json::Error> for CommandError {
     fn from(value: serde_json::Error) -> Self {
         Self::SerdeJson(value)
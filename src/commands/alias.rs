@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use super::config::group_admin_guard;
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::config::CommandAlias;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+
+const MAX_ALIASES: usize = 50;
+const MAX_NAME_LENGTH: usize = 32;
+
+pub struct Alias;
+
+#[async_trait]
+impl CommandTrait for Alias {
+    fn command_names(&self) -> &[&str] {
+        &["alias", "aliases"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("list or define custom commands in this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats, MenuScope::ChatAdministrators]
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("[name = /command arguments]")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["", "pl = /tr en pl"]
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        if arguments.trim_ascii().is_empty() {
+            return list_aliases(ctx).await;
+        }
+
+        group_admin_guard(ctx).await?;
+
+        let (name, alias) = parse_alias(ctx.language(), &arguments)?;
+
+        let target = ctx
+            .bot_state
+            .command_manager
+            .lock()
+            .unwrap()
+            .get_command(&alias.command)
+            .ok_or(i18n::text(ctx.language(), "unknown_command"))?;
+
+        if ctx.bot_state.command_manager.lock().unwrap().get_command(&name).is_some() {
+            return Err(i18n::text(ctx.language(), "alias_builtin").into());
+        }
+
+        let alias = CommandAlias { command: target.name().into(), arguments: alias.arguments };
+
+        {
+            let mut config = ctx.bot_state.config.lock().unwrap();
+            let aliases = config.command_aliases.entry(ctx.message.chat_id).or_default();
+
+            if !aliases.contains_key(&name) && aliases.len() >= MAX_ALIASES {
+                return Err(i18n::format(
+                    ctx.language(),
+                    "alias_limit",
+                    &[("limit", &MAX_ALIASES)],
+                )
+                .into());
+            }
+
+            aliases.insert(name.clone(), alias.clone());
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            format!("/{name}").code_owned(),
+            i18n::text(ctx.language(), "alias_defined").text(),
+            format_alias(&alias).code_owned(),
+            ".".text(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub struct Unalias;
+
+#[async_trait]
+impl CommandTrait for Unalias {
+    fn command_names(&self) -> &[&str] {
+        &["unalias"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("remove a custom command from this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats, MenuScope::ChatAdministrators]
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<name>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        group_admin_guard(ctx).await?;
+
+        let name = arguments.trim_ascii().trim_start_matches('/').to_ascii_lowercase();
+
+        let removed = {
+            let mut config = ctx.bot_state.config.lock().unwrap();
+
+            let removed = config
+                .command_aliases
+                .get_mut(&ctx.message.chat_id)
+                .and_then(|aliases| aliases.remove(&name));
+
+            if config.command_aliases.get(&ctx.message.chat_id).is_some_and(BTreeMap::is_empty) {
+                config.command_aliases.remove(&ctx.message.chat_id);
+            }
+
+            removed
+        };
+
+        if removed.is_none() {
+            return Err(i18n::text(ctx.language(), "no_such_alias").into());
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            format!("/{name}").code_owned(),
+            i18n::text(ctx.language(), "alias_removed").text(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
+
+async fn list_aliases(ctx: &CommandContext) -> CommandResult {
+    let aliases = ctx
+        .bot_state
+        .config
+        .lock()
+        .unwrap()
+        .command_aliases
+        .get(&ctx.message.chat_id)
+        .cloned()
+        .unwrap_or_default();
+
+    if aliases.is_empty() {
+        return Err(i18n::text(ctx.language(), "no_aliases").into());
+    }
+
+    let mut entities = vec![i18n::text(ctx.language(), "alias_list").text()];
+
+    for (name, alias) in aliases {
+        entities.push("\n".text());
+        entities.push(format!("/{name}").code_owned());
+        entities.push(" → ".text());
+        entities.push(format_alias(&alias).code_owned());
+    }
+
+    ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+    Ok(())
+}
+
+/// parses `name = /command arguments`
+fn parse_alias(
+    language: Language,
+    arguments: &str,
+) -> Result<(String, CommandAlias), CommandError> {
+    let (name, target) = arguments.split_once('=').ok_or(i18n::text(language, "alias_usage"))?;
+
+    let name = name.trim_ascii().trim_start_matches('/').to_ascii_lowercase();
+
+    if name.is_empty()
+        || name.len() > MAX_NAME_LENGTH
+        || !name.chars().all(|char| char.is_ascii_alphanumeric() || char == '_')
+    {
+        return Err(
+            i18n::format(language, "alias_invalid_name", &[("limit", &MAX_NAME_LENGTH)]).into()
+        );
+    }
+
+    let target = target.trim_ascii().trim_start_matches('/');
+    let (command, arguments) = target.split_once(char::is_whitespace).unwrap_or((target, ""));
+    let command = command.split_once('@').map_or(command, |(command, _)| command);
+
+    if command.is_empty() {
+        return Err(i18n::text(language, "alias_missing_command").into());
+    }
+
+    Ok((
+        name,
+        CommandAlias {
+            command: command.to_ascii_lowercase(),
+            arguments: arguments.trim_ascii().into(),
+        },
+    ))
+}
+
+fn format_alias(alias: &CommandAlias) -> String {
+    if alias.arguments.is_empty() {
+        format!("/{}", alias.command)
+    } else {
+        format!("/{} {}", alias.command, alias.arguments)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_alias() {
+        let (name, alias) = parse_alias(Language::English, "/PL = /tr@bot en  pl").unwrap();
+        assert_eq!(name, "pl");
+        assert_eq!(alias.command, "tr");
+        assert_eq!(alias.arguments, "en  pl");
+        assert_eq!(alias.expand("hello"), "en  pl hello");
+
+        let (_, alias) = parse_alias(Language::English, "dl=/dla").unwrap();
+        assert_eq!(alias.command, "dla");
+        assert_eq!(alias.expand(""), "");
+
+        assert!(parse_alias(Language::English, "pl /tr en pl").is_err());
+        assert!(parse_alias(Language::English, "p-l = /tr").is_err());
+        assert!(parse_alias(Language::English, "pl = ").is_err());
+    }
+}
@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use rand::seq::IteratorRandom;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::google;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n;
+use crate::utilities::rate_limit::RateLimit;
 
 pub struct Autocomplete;
 
@@ -19,19 +21,39 @@ impl CommandTrait for Autocomplete {
         Some("autocompletes a query with Google")
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(10, 30)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<query>")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["how to"]
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(10, 30)]
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(query) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        let completions =
-            google::complete(ctx.bot_state.http_client.clone(), &query).await.unwrap_or_default();
+        let completions = ctx
+            .bot_state
+            .response_cache
+            .get_or_fetch(
+                Endpoint::Google,
+                query.as_str(),
+                google::complete(ctx.bot_state.http_client.clone(), &query),
+            )
+            .await
+            .unwrap_or_default();
 
-        let completion = completions.into_iter().choose(&mut rand::rng());
+        let completion = completions.iter().choose(&mut rand::rng()).cloned();
 
-        ctx.reply(completion.ok_or("no autocompletions")?).await?;
+        ctx.reply(completion.ok_or(i18n::text(ctx.language(), "no_autocompletions"))?).await?;
 
         Ok(())
     }
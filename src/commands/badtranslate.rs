@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::translate;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
@@ -18,6 +18,14 @@ impl CommandTrait for BadTranslate {
         Some("badly translate text by translating every word separately")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<text>")
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let (SourceTargetLanguages(source_language, target_language), StringGreedyOrReply(text)) =
             ConvertArgument::convert(ctx, &arguments).await?.0;
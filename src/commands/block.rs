@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use tdlib::enums::MessageSender;
+use tdlib::types::MessageSenderUser;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::settings::{self, Role};
+
+pub struct Block;
+
+#[async_trait]
+impl CommandTrait for Block {
+    fn command_names(&self) -> &[&str] {
+        &["block"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let user_id = target_user(ctx, &arguments).await?;
+
+        if settings::get().role(user_id) != Role::User {
+            return Err("privileged users can't be blocked.".into());
+        }
+
+        if !ctx.bot_state.config.lock().unwrap().blocked_users.insert(user_id) {
+            return Err("this user is already blocked.".into());
+        }
+
+        ctx.reply(format!("blocked user {user_id}.")).await?;
+
+        Ok(())
+    }
+}
+
+pub struct Unblock;
+
+#[async_trait]
+impl CommandTrait for Unblock {
+    fn command_names(&self) -> &[&str] {
+        &["unblock"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let user_id = target_user(ctx, &arguments).await?;
+
+        if !ctx.bot_state.config.lock().unwrap().blocked_users.remove(&user_id) {
+            return Err("this user isn't blocked.".into());
+        }
+
+        ctx.reply(format!("unblocked user {user_id}.")).await?;
+
+        Ok(())
+    }
+}
+
+/// user ID passed as the argument, or the sender of the replied message
+async fn target_user(ctx: &CommandContext, arguments: &str) -> Result<i64, CommandError> {
+    let arguments = arguments.trim();
+
+    if !arguments.is_empty() {
+        return arguments.parse().map_err(|_| "invalid user ID.".into());
+    }
+
+    if ctx.message.reply_to.is_none() {
+        return Err("specify a user ID or reply to a message.".into());
+    }
+
+    let message = ctx.client.get_replied_message(ctx.message.chat_id, ctx.message.id).await?;
+
+    let MessageSender::User(MessageSenderUser { user_id }) = message.sender_id else {
+        return Err("the replied message wasn't sent by a user.".into());
+    };
+
+    Ok(user_id)
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tdlib::enums::InputMessageContent;
+use tdlib::functions;
+use tdlib::types::{FormattedText, InputMessageText};
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::settings::Role;
+
+pub struct Broadcast;
+
+#[async_trait]
+impl CommandTrait for Broadcast {
+    fn command_names(&self) -> &[&str] {
+        &["broadcast"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let chat_ids = ctx.bot_state.cache.lock().unwrap().group_chat_ids();
+        let status_message =
+            ctx.reply(format!("broadcasting to {} chats…", chat_ids.len())).await?;
+
+        let mut failed = 0;
+        for chat_id in &chat_ids {
+            let result = functions::send_message(
+                *chat_id,
+                None,
+                None,
+                None,
+                None,
+                InputMessageContent::InputMessageText(InputMessageText {
+                    text: FormattedText { text: text.clone(), ..Default::default() },
+                    ..Default::default()
+                }),
+                ctx.client_id,
+            )
+            .await;
+
+            if let Err(err) = result {
+                log::warn!("failed to broadcast to {chat_id}: {}", err.message);
+                failed += 1;
+            }
+
+            // stay well below the global limit of 30 messages per second
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        ctx.edit_message(
+            status_message.id,
+            format!("broadcasted to {} chats, {failed} failed.", chat_ids.len() - failed),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use tdlib::enums::{InputInlineQueryResult, InputMessageContent};
 use tdlib::functions;
 use tdlib::types::{
@@ -6,9 +8,13 @@ use tdlib::types::{
 };
 
 use crate::apis::mathjs;
+use crate::utilities::bot_state::BotState;
+use crate::utilities::command_dispatcher;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n::{self, Language};
 
-pub async fn execute(query: UpdateNewInlineQuery, http_client: reqwest::Client, client_id: i32) {
-    let (query_id, query) = (query.id, query.query);
+pub async fn execute(query: UpdateNewInlineQuery, bot_state: Arc<BotState>, client_id: i32) {
+    let (query_id, user_id, query) = (query.id, query.sender_user_id, query.query);
 
     if query.is_empty() {
         functions::answer_inline_query(
@@ -29,12 +35,55 @@ pub async fn execute(query: UpdateNewInlineQuery, http_client: reqwest::Client,
     let (title, message_text) = if query.split_ascii_whitespace().collect::<String>() == "2+2" {
         ("5".into(), format!("{query} = 5"))
     } else {
-        match mathjs::evaluate(http_client.clone(), query.clone()).await.unwrap() {
+        let result = bot_state
+            .response_cache
+            .get_or_fetch(
+                Endpoint::Mathjs,
+                query.as_str(),
+                mathjs::evaluate(bot_state.http_client.clone(), query.clone()),
+            )
+            .await;
+
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                let user = bot_state.cache.lock().unwrap().get_user(user_id);
+                let language =
+                    user.map_or(Language::English, |user| i18n::language(&bot_state, None, &user));
+                let text = command_dispatcher::format_command_error(
+                    &"inline calculator",
+                    language,
+                    err.into(),
+                );
+
+                answer(query_id, text.text.clone(), text, 0, client_id).await;
+                return;
+            }
+        };
+
+        match &*result {
             Ok(result) => (result.clone(), format!("{query} = {result}")),
-            Err(err) => (err.clone(), err),
+            Err(err) => (err.clone(), err.clone()),
         }
     };
 
+    answer(
+        query_id,
+        title,
+        FormattedText { text: message_text, ..Default::default() },
+        3600,
+        client_id,
+    )
+    .await;
+}
+
+async fn answer(
+    query_id: i64,
+    title: String,
+    text: FormattedText,
+    cache_time: i32,
+    client_id: i32,
+) {
     functions::answer_inline_query(
         query_id,
         false,
@@ -49,7 +98,7 @@ pub async fn execute(query: UpdateNewInlineQuery, http_client: reqwest::Client,
             thumbnail_height: 0,
             reply_markup: None,
             input_message_content: InputMessageContent::InputMessageText(InputMessageText {
-                text: FormattedText { text: message_text, ..Default::default() },
+                text,
                 link_preview_options: Some(LinkPreviewOptions {
                     is_disabled: true,
                     ..Default::default()
@@ -57,7 +106,7 @@ pub async fn execute(query: UpdateNewInlineQuery, http_client: reqwest::Client,
                 ..Default::default()
             }),
         })],
-        3600,
+        cache_time,
         String::new(),
         client_id,
     )
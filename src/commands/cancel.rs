@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::i18n;
+
+pub struct Cancel;
+
+#[async_trait]
+impl CommandTrait for Cancel {
+    fn command_names(&self) -> &[&str] {
+        &["cancel", "stop"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("cancel your running commands")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let cancelled = ctx.bot_state.running_commands.lock().unwrap().cancel(
+            ctx.message.chat_id,
+            ctx.user.id,
+            ctx.message.id,
+        );
+
+        if cancelled.is_empty() {
+            return Err(i18n::text(ctx.language(), "no_running_commands").into());
+        }
+
+        let count = cancelled.len();
+
+        for cleanup in cancelled.into_iter().flatten() {
+            cleanup
+                .run(ctx.message.chat_id, ctx.bot_state.http_client.clone(), ctx.client_id)
+                .await;
+        }
+
+        ctx.reply(i18n::format_plural(ctx.language(), "cancelled_commands", count)).await?;
+
+        Ok(())
+    }
+}
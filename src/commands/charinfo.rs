@@ -1,8 +1,13 @@
 use async_trait::async_trait;
+use tdlib::types::FormattedText;
 
-use super::{CommandResult, CommandTrait};
+use super::{
+    CommandCategory, CommandResult, CommandTrait, InlineCommandResult, InlineCommandTrait,
+};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::i18n;
+use crate::utilities::inline_query_context::{InlineArticle, InlineQueryContext};
 use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
 
 pub struct CharInfo;
@@ -17,37 +22,80 @@ impl CommandTrait for CharInfo {
         Some("get Unicode character names")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<text>")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["ą→😀"]
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(chars) = ConvertArgument::convert(ctx, &arguments).await?.0;
-        let mut chars = chars.chars();
-
-        let mut entities = chars
-            .by_ref()
-            .take(10)
-            .flat_map(|char| {
-                if char.is_ascii_whitespace() {
-                    vec!["\n".text()]
-                } else {
-                    let value = char.into();
-                    vec![
-                        "\n".text(),
-                        char.to_string().code_owned(),
-                        " ".text(),
-                        format!("U+{value:04X}").code_owned(),
-                        " – ".text(),
-                        charname::get_name(value).code(),
-                    ]
-                }
-            })
-            .skip(1)
-            .collect::<Vec<_>>();
-
-        if chars.next().is_some() {
-            entities.push("…".text());
-        }
 
-        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+        ctx.reply_formatted_text(format_chars(&chars)).await?;
 
         Ok(())
     }
 }
+
+#[async_trait]
+impl InlineCommandTrait for CharInfo {
+    fn inline_prefixes(&self) -> &[&str] {
+        &["ch", "charinfo"]
+    }
+
+    async fn execute_inline(
+        &self,
+        ctx: &InlineQueryContext,
+        arguments: String,
+    ) -> InlineCommandResult {
+        let chars = arguments.trim_ascii();
+        if chars.is_empty() {
+            return Err(i18n::text(ctx.language(), "charinfo_no_text").into());
+        }
+
+        let text = format_chars(chars);
+
+        Ok(InlineArticle {
+            title: i18n::text(ctx.language(), "charinfo_title").into(),
+            description: text.text.clone(),
+            text,
+        })
+    }
+}
+
+fn format_chars(chars: &str) -> FormattedText {
+    let mut chars = chars.chars();
+
+    let mut entities = chars
+        .by_ref()
+        .take(10)
+        .flat_map(|char| {
+            if char.is_ascii_whitespace() {
+                vec!["\n".text()]
+            } else {
+                let value = char.into();
+                vec![
+                    "\n".text(),
+                    char.to_string().code_owned(),
+                    " ".text(),
+                    format!("U+{value:04X}").code_owned(),
+                    " – ".text(),
+                    charname::get_name(value).code(),
+                ]
+            }
+        })
+        .skip(1)
+        .collect::<Vec<_>>();
+
+    if chars.next().is_some() {
+        entities.push("…".text());
+    }
+
+    message_entities::formatted_text(entities)
+}
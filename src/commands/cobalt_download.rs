@@ -1,10 +1,8 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::env;
 use std::path::Path;
 
 use async_trait::async_trait;
-use serde::Deserialize;
 use serde_json::Value;
 use tdlib::enums::{InputFile, InputMessageContent, InputMessageReplyTo, Messages};
 use tdlib::functions;
@@ -14,13 +12,15 @@ use tdlib::types::{
 };
 use url::Url;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
 use crate::apis::cobalt::{self, Error, ErrorContext, Response};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
 use crate::utilities::file_download::NetworkFile;
+use crate::utilities::job_queue::ConcurrencyClass;
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::{ffprobe, telegram_utils};
+use crate::utilities::running_commands::Cleanup;
+use crate::utilities::{ffprobe, i18n, settings, telegram_utils};
 
 const TWITTER_REPLACEMENTS: [&str; 7] = [
     "fxtwitter.com",
@@ -32,13 +32,6 @@ const TWITTER_REPLACEMENTS: [&str; 7] = [
     "stupidpenisx.com",
 ];
 
-#[derive(Deserialize)]
-struct CobaltInstance<'a> {
-    name: &'a str,
-    url: &'a str,
-    api_key: Option<&'a str>,
-}
-
 pub struct CobaltDownload {
     command_names: &'static [&'static str],
     description: &'static str,
@@ -73,9 +66,29 @@ impl CommandTrait for CobaltDownload {
         Some(self.description)
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Media
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<url>")
+    }
+
+    fn concurrency_class(&self) -> Option<ConcurrencyClass> {
+        Some(ConcurrencyClass::HeavyMedia)
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(media_url) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
+        if settings::get().cobalt_instances.is_empty() {
+            return Err(i18n::text(ctx.language(), "no_cobalt_instances").into());
+        }
+
         ctx.send_typing().await?;
 
         let (instance, result) =
@@ -106,14 +119,20 @@ async fn get_result(
         .map(|path| Cow::Owned(format!("https://twitter.com/{path}")))
         .unwrap_or(Cow::Borrowed(url));
 
-    let instances = env::var("COBALT_INSTANCES").unwrap();
-    let instances = serde_json::from_str::<Vec<CobaltInstance>>(&instances).unwrap();
-
+    let settings = settings::get();
     let mut error = None;
 
-    for instance in instances {
-        match cobalt::query(http_client, instance.url, instance.api_key, &url, audio_only).await {
-            Ok(result) => return Ok((instance.name.into(), result)),
+    for instance in &settings.cobalt_instances {
+        match cobalt::query(
+            http_client,
+            &instance.url,
+            instance.api_key.as_deref(),
+            &url,
+            audio_only,
+        )
+        .await
+        {
+            Ok(result) => return Ok((instance.name.clone(), result)),
             Err(err) => {
                 error = Some(err);
             }
@@ -137,7 +156,7 @@ async fn send_files(
                 .message_queue
                 .wait_for_message(
                     ctx.reply_formatted_text(message_entities::formatted_text(vec![
-                        "downloading from ".text(),
+                        i18n::text(ctx.language(), "downloading_from").text(),
                         instance.code(),
                         "…".text(),
                     ]))
@@ -146,6 +165,8 @@ async fn send_files(
                 )
                 .await?;
 
+            ctx.add_cleanup(Cleanup::DeleteMessage(status_msg.id));
+
             let network_file = NetworkFile::download(
                 &ctx.bot_state.http_client,
                 &file.url,
@@ -154,7 +175,7 @@ async fn send_files(
             )
             .await?;
 
-            ctx.edit_message(status_msg.id, "uploading…".into()).await?;
+            ctx.edit_message(status_msg.id, i18n::text(ctx.language(), "uploading").into()).await?;
 
             ctx.bot_state
                 .message_queue
@@ -181,7 +202,7 @@ async fn send_files(
                 .message_queue
                 .wait_for_message(
                     ctx.reply_formatted_text(message_entities::formatted_text(vec![
-                        "downloading from ".text(),
+                        i18n::text(ctx.language(), "downloading_from").text(),
                         instance.code(),
                         "…".text(),
                     ]))
@@ -190,6 +211,8 @@ async fn send_files(
                 )
                 .await?;
 
+            ctx.add_cleanup(Cleanup::DeleteMessage(status_msg.id));
+
             let mut picker_items = picker.picker;
             picker_items.truncate(10);
             let mut files = Vec::with_capacity(picker_items.len());
@@ -220,7 +243,7 @@ async fn send_files(
                 None
             };
 
-            ctx.edit_message(status_msg.id, "uploading…".into()).await?;
+            ctx.edit_message(status_msg.id, i18n::text(ctx.language(), "uploading").into()).await?;
 
             let messages = files
                 .iter()
@@ -317,7 +340,7 @@ async fn send_files(
             return Err(CommandError::CustomFormattedText(message_entities::formatted_text(vec![
                 error_text.text(),
                 "\n".text(),
-                "download using cobalt.tools".text_url(cobalt_url.as_str()),
+                i18n::text(ctx.language(), "cobalt_download_link").text_url(cobalt_url.as_str()),
             ])));
         }
     }
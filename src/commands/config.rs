@@ -1,13 +1,19 @@
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
 use tdlib::enums::{ChatMemberStatus, ChatType};
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::ConvertArgument;
-use crate::utilities::message_entities::{self, Entity, ToEntity};
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::message_entities::{self, Entity, ToEntity, ToEntityOwned};
 
 const MARKOV_CHAIN_LEARNING: &str = "markov_chain_learning";
-const SETTINGS: [&str; 1] = [MARKOV_CHAIN_LEARNING];
+const COMMAND_ENABLED: &str = "command_enabled";
+const COMMAND_ADMIN_ONLY: &str = "command_admin_only";
+const LANGUAGE: &str = "language";
+const SETTINGS: [&str; 4] = [MARKOV_CHAIN_LEARNING, COMMAND_ENABLED, COMMAND_ADMIN_ONLY, LANGUAGE];
 
 pub struct Config;
 
@@ -21,9 +27,38 @@ impl CommandTrait for Config {
         Some("configure bot settings")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats, MenuScope::ChatAdministrators]
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("[setting] [value]")
+    }
+
+    fn arguments(&self) -> &[(&'static str, &'static str)] {
+        &[
+            ("markov_chain_learning <on|off>", "learn from messages in this chat"),
+            (
+                "command_enabled <command> <on|off>",
+                "enable or disable a command in this chat, except for bot commands",
+            ),
+            ("command_admin_only <command> <on|off>", "restrict a command to chat admins"),
+            ("language <code|auto>", "set the bot language in this chat"),
+        ]
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["language pl", "command_enabled sex off"]
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let Ok((mut setting, rest)) = String::convert(ctx, &arguments).await else {
-            let mut entities = vec!["list of available settings:\n".text()];
+            let mut entities =
+                vec![format!("{}\n", i18n::text(ctx.language(), "settings_list")).text_owned()];
             entities.extend(setting_names());
             ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
             return Ok(());
@@ -31,53 +66,201 @@ impl CommandTrait for Config {
 
         setting.make_ascii_lowercase();
 
-        if setting == MARKOV_CHAIN_LEARNING {
-            chat_group_guard(ctx)?;
-            chat_admin_guard(ctx).await?;
-
-            let value = bool::convert(ctx, rest).await?.0;
-            if value {
-                let changed = ctx
-                    .bot_state
-                    .config
-                    .lock()
-                    .unwrap()
-                    .markov_chain_learning
-                    .insert(ctx.message.chat_id);
-                if changed {
-                    ctx.reply("Markov chain will now learn from chat messages.".into()).await?;
-                } else {
-                    ctx.reply("Markov chain learning was already enabled.".into()).await?;
-                }
-            } else {
-                let changed = ctx
-                    .bot_state
-                    .config
-                    .lock()
-                    .unwrap()
-                    .markov_chain_learning
-                    .remove(&ctx.message.chat_id);
-                if changed {
-                    ctx.reply("Markov chain won't learn from chat messages anymore.".into())
-                        .await?;
-                } else {
-                    ctx.reply("Markov chain learning was already disabled.".into()).await?;
-                }
+        match setting.as_str() {
+            MARKOV_CHAIN_LEARNING => markov_chain_learning(ctx, rest).await?,
+            COMMAND_ENABLED => command_enabled(ctx, rest).await?,
+            COMMAND_ADMIN_ONLY => command_admin_only(ctx, rest).await?,
+            LANGUAGE => language(ctx, rest).await?,
+            _ => {
+                let mut entities = vec![
+                    format!("{}\n", i18n::text(ctx.language(), "unknown_setting")).text_owned(),
+                ];
+                entities.extend(setting_names());
+
+                Err(CommandError::CustomFormattedText(message_entities::formatted_text(entities)))?;
             }
+        }
+
+        Ok(())
+    }
+}
+
+async fn markov_chain_learning(ctx: &CommandContext, arguments: &str) -> CommandResult {
+    chat_group_guard(ctx)?;
+    chat_admin_guard(ctx).await?;
+
+    let value = bool::convert(ctx, arguments).await?.0;
+    if value {
+        let changed =
+            ctx.bot_state.config.lock().unwrap().markov_chain_learning.insert(ctx.message.chat_id);
+        let text = if changed {
+            i18n::text(ctx.language(), "markov_learning_enabled")
         } else {
-            let mut entities = vec!["unknown setting name. available settings include:\n".text()];
-            entities.extend(setting_names());
+            i18n::text(ctx.language(), "markov_learning_already_enabled")
+        };
+        ctx.reply(text.into()).await?;
+    } else {
+        let changed =
+            ctx.bot_state.config.lock().unwrap().markov_chain_learning.remove(&ctx.message.chat_id);
+        let text = if changed {
+            i18n::text(ctx.language(), "markov_learning_disabled")
+        } else {
+            i18n::text(ctx.language(), "markov_learning_already_disabled")
+        };
+        ctx.reply(text.into()).await?;
+    }
+
+    Ok(())
+}
+
+async fn command_enabled(ctx: &CommandContext, arguments: &str) -> CommandResult {
+    chat_group_guard(ctx)?;
+    chat_admin_guard(ctx).await?;
+
+    let (command_name, rest) = command_argument(ctx, arguments).await?;
+    let value = bool::convert(ctx, rest).await?.0;
+
+    let changed = update_command_set(
+        &mut ctx.bot_state.config.lock().unwrap().disabled_commands,
+        ctx.message.chat_id,
+        &command_name,
+        !value,
+    );
 
-            Err(CommandError::CustomFormattedText(message_entities::formatted_text(entities)))?;
+    let text = match (value, changed) {
+        (true, true) => i18n::text(ctx.language(), "command_enabled_now"),
+        (true, false) => i18n::text(ctx.language(), "command_enabled_already"),
+        (false, true) => i18n::text(ctx.language(), "command_disabled_now"),
+        (false, false) => i18n::text(ctx.language(), "command_disabled_already"),
+    };
+
+    ctx.reply_formatted_text(message_entities::formatted_text(vec![
+        format!("/{command_name}").code_owned(),
+        " ".text(),
+        text.text(),
+    ]))
+    .await?;
+
+    Ok(())
+}
+
+async fn command_admin_only(ctx: &CommandContext, arguments: &str) -> CommandResult {
+    chat_group_guard(ctx)?;
+    chat_admin_guard(ctx).await?;
+
+    let (command_name, rest) = command_argument(ctx, arguments).await?;
+    let value = bool::convert(ctx, rest).await?.0;
+
+    let changed = update_command_set(
+        &mut ctx.bot_state.config.lock().unwrap().admin_only_commands,
+        ctx.message.chat_id,
+        &command_name,
+        value,
+    );
+
+    let text = match (value, changed) {
+        (true, true) => i18n::text(ctx.language(), "command_admin_only_now"),
+        (true, false) => i18n::text(ctx.language(), "command_admin_only_already"),
+        (false, true) => i18n::text(ctx.language(), "command_everyone_now"),
+        (false, false) => i18n::text(ctx.language(), "command_everyone_already"),
+    };
+
+    ctx.reply_formatted_text(message_entities::formatted_text(vec![
+        format!("/{command_name}").code_owned(),
+        " ".text(),
+        text.text(),
+    ]))
+    .await?;
+
+    Ok(())
+}
+
+async fn language(ctx: &CommandContext, arguments: &str) -> CommandResult {
+    group_admin_guard(ctx).await?;
+
+    let mut code = String::convert(ctx, arguments).await?.0;
+    code.make_ascii_lowercase();
+
+    if code == "auto" {
+        ctx.bot_state.config.lock().unwrap().chat_languages.remove(&ctx.message.chat_id);
+        ctx.reply(i18n::text(ctx.language(), "language_reset").into()).await?;
+
+        return Ok(());
+    }
+
+    let Some(language) = Language::from_code(&code) else {
+        let languages = Language::ALL.map(Language::code).join(", ");
+
+        return Err(i18n::format(
+            ctx.language(),
+            "unknown_language",
+            &[("languages", &format!("{languages}, auto"))],
+        )
+        .into());
+    };
+
+    ctx.bot_state
+        .config
+        .lock()
+        .unwrap()
+        .chat_languages
+        .insert(ctx.message.chat_id, language.code().into());
+
+    ctx.reply(i18n::format(language, "language_set", &[("language", &language.name())])).await?;
+
+    Ok(())
+}
+
+async fn command_argument<'a>(
+    ctx: &CommandContext,
+    arguments: &'a str,
+) -> Result<(String, &'a str), CommandError> {
+    let (mut command_name, rest) = String::convert(ctx, arguments).await?;
+    command_name.make_ascii_lowercase();
+
+    let command = ctx
+        .bot_state
+        .command_manager
+        .lock()
+        .unwrap()
+        .get_command(command_name.trim_start_matches('/'))
+        .ok_or_else(|| i18n::text(ctx.language(), "unknown_command"))?;
+
+    // bot commands manage the chat's settings, aliases and notes, so disabling
+    // them could lock the admins out of undoing it
+    if command.command.category() == CommandCategory::Bot {
+        return Err(i18n::text(ctx.language(), "command_not_restrictable").into());
+    }
+
+    Ok((command.name().into(), rest))
+}
+
+fn update_command_set(
+    commands: &mut HashMap<i64, HashSet<String>>,
+    chat_id: i64,
+    command_name: &str,
+    value: bool,
+) -> bool {
+    if value {
+        commands.entry(chat_id).or_default().insert(command_name.into())
+    } else {
+        let Some(chat_commands) = commands.get_mut(&chat_id) else {
+            return false;
+        };
+
+        let changed = chat_commands.remove(command_name);
+
+        if chat_commands.is_empty() {
+            commands.remove(&chat_id);
         }
 
-        Ok(())
+        changed
     }
 }
 
 fn chat_group_guard(ctx: &CommandContext) -> CommandResult {
     let (ChatType::BasicGroup(_) | ChatType::Supergroup(_)) = ctx.chat.r#type else {
-        return Err("this setting can be only set in groups.".into());
+        return Err(i18n::text(ctx.language(), "setting_groups_only").into());
     };
 
     Ok(())
@@ -92,7 +275,16 @@ async fn chat_admin_guard(ctx: &CommandContext) -> CommandResult {
         ChatMemberStatus::Administrator(status) => status.rights.can_change_info,
         _ => false,
     } {
-        return Err("this setting requires the Change Group Info permission.".into());
+        return Err(i18n::text(ctx.language(), "setting_requires_change_info").into());
+    }
+
+    Ok(())
+}
+
+/// requires admin rights in groups, and nothing in private chats
+pub async fn group_admin_guard(ctx: &CommandContext) -> CommandResult {
+    if let ChatType::BasicGroup(_) | ChatType::Supergroup(_) = ctx.chat.r#type {
+        chat_admin_guard(ctx).await?;
     }
 
     Ok(())
@@ -101,3 +293,21 @@ async fn chat_admin_guard(ctx: &CommandContext) -> CommandResult {
 fn setting_names() -> impl Iterator<Item = Entity<'static>> {
     SETTINGS.into_iter().flat_map(|setting| [",\n".text(), setting.code()]).skip(1)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_update_command_set() {
+        let mut commands = HashMap::new();
+
+        assert!(update_command_set(&mut commands, 1, "sex", true));
+        assert!(!update_command_set(&mut commands, 1, "sex", true));
+        assert!(commands[&1].contains("sex"));
+
+        assert!(!update_command_set(&mut commands, 2, "sex", false));
+        assert!(update_command_set(&mut commands, 1, "sex", false));
+        assert!(!commands.contains_key(&1));
+    }
+}
@@ -1,20 +1,26 @@
 //! inspired by <https://github.com/lunush/rates>
 
 use std::borrow::Cow;
-use std::time::{Duration, Instant};
 use std::{fmt, iter};
 
 use async_trait::async_trait;
+use tdlib::types::FormattedText;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{
+    CommandCategory, CommandError, CommandResult, CommandTrait, InlineCommandResult,
+    InlineCommandTrait, MenuScope,
+};
 use crate::apis::coinranking::Coin;
 use crate::apis::eurofxref::Rate;
 use crate::apis::{coinranking, eurofxref};
-use crate::utilities::bot_state::Currencies;
+use crate::utilities::bot_state::BotState;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConversionError, ConvertArgument};
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::inline_query_context::{InlineArticle, InlineQueryContext};
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::RateLimit;
 
 struct Arguments {
     amount: f64,
@@ -22,53 +28,39 @@ struct Arguments {
     target_currencies: Vec<Cow<'static, str>>,
 }
 
-#[async_trait]
-impl ConvertArgument for Arguments {
-    async fn convert<'a>(
-        ctx: &CommandContext,
-        arguments: &'a str,
-    ) -> Result<(Self, &'a str), ConversionError> {
-        let (part, mut arguments) = Option::<String>::convert(ctx, arguments).await?;
+impl Arguments {
+    fn parse(language: Language, arguments: &str) -> Result<Self, ConversionError> {
+        let mut words = arguments.split_ascii_whitespace();
 
-        let (amount, currency) = match part {
+        let (amount, currency) = match words.next() {
             Some(part) => match part.parse::<f64>() {
                 Ok(amount) => {
                     if !amount.is_normal() {
-                        return Err(ConversionError::BadArgument(Cow::Owned(format!(
-                            "{amount} is not a valid amount."
+                        return Err(ConversionError::BadArgument(Cow::Owned(i18n::format(
+                            language,
+                            "invalid_amount",
+                            &[("amount", &amount)],
                         ))));
                     }
 
-                    let (source_currency, rest) = String::convert(ctx, arguments).await?;
-                    arguments = rest;
-                    (amount, source_currency)
+                    let source_currency = words.next().ok_or(ConversionError::MissingArgument)?;
+                    (amount, source_currency.into())
                 }
-                Err(_) => (1., part),
+                Err(_) => (1., part.into()),
             },
             None => return Err(ConversionError::MissingArgument),
         };
 
         let mut target_currencies = Vec::new();
 
-        loop {
-            let (part, rest) = Option::<String>::convert(ctx, arguments).await?;
-            arguments = rest;
-
-            match part {
-                Some(part) => {
-                    if part.eq_ignore_ascii_case("to") {
-                        let (currency, rest) = Option::<String>::convert(ctx, arguments).await?;
-                        arguments = rest;
-
-                        match currency {
-                            Some(currency) => target_currencies.push(Cow::Owned(currency)),
-                            None => break,
-                        }
-                    } else {
-                        target_currencies.push(Cow::Owned(part));
-                    }
+        while let Some(part) = words.next() {
+            if part.eq_ignore_ascii_case("to") {
+                match words.next() {
+                    Some(currency) => target_currencies.push(Cow::Owned(currency.into())),
+                    None => break,
                 }
-                None => break,
+            } else {
+                target_currencies.push(Cow::Owned(part.into()));
             }
         }
 
@@ -76,7 +68,17 @@ impl ConvertArgument for Arguments {
             target_currencies.push(Cow::Borrowed("usd"));
         }
 
-        Ok((Self { amount, currency, target_currencies }, arguments))
+        Ok(Self { amount, currency, target_currencies })
+    }
+}
+
+#[async_trait]
+impl ConvertArgument for Arguments {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        Ok((Self::parse(ctx.language(), arguments)?, ""))
     }
 }
 
@@ -109,69 +111,29 @@ impl CommandTrait for Convert {
         Some("convert between popular currencies and cryptocurrencies")
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(10, 30)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
     }
 
-    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let arguments = Arguments::convert(ctx, &arguments).await?.0;
-
-        let mut currencies = ctx.bot_state.currencies.lock().await;
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
 
-        let currencies = match *currencies {
-            Some(ref currencies) if currencies.updated_at.elapsed() < Duration::from_hours(1) => {
-                currencies
-            }
-            _ => {
-                let fiat = eurofxref::daily(&ctx.bot_state.http_client).await?;
-                let crypto = coinranking::coins(&ctx.bot_state.http_client).await?;
-                *currencies = Some(Currencies { updated_at: Instant::now(), fiat, crypto });
-                currencies.as_ref().unwrap()
-            }
-        };
+    fn usage(&self) -> Option<&'static str> {
+        Some("[amount] <currency> [to <currency>…]")
+    }
 
-        let (source_currency, amount_eur) =
-            match get_fiat_rate(&currencies.fiat, &arguments.currency) {
-                Some((currency, rate)) => (currency, arguments.amount / rate),
-                None => match get_crypto_price(&currencies.crypto, &arguments.currency) {
-                    Some((currency, price)) => (currency, arguments.amount * price),
-                    None => {
-                        return Err(CommandError::CustomFormattedText(
-                            message_entities::formatted_text(vec![
-                                "could not find source currency ".text(),
-                                arguments.currency.code(),
-                            ]),
-                        ));
-                    }
-                },
-            };
-
-        let target_currencies = iter::once(Ok((source_currency, arguments.amount)))
-            .chain(arguments.target_currencies.into_iter().map(
-                |target_currency| match get_fiat_rate(&currencies.fiat, &target_currency) {
-                    Some((symbol, rate)) => Ok((symbol, amount_eur * rate)),
-                    None => match get_crypto_price(&currencies.crypto, &target_currency) {
-                        Some((symbol, price)) => Ok((symbol, amount_eur / price)),
-                        None => Err(CommandError::CustomFormattedText(
-                            message_entities::formatted_text(vec![
-                                "could not find target currency ".text(),
-                                target_currency.code(),
-                            ]),
-                        )),
-                    },
-                },
-            ))
-            .collect::<Result<Vec<_>, _>>()?;
+    fn examples(&self) -> &[&'static str] {
+        &["10 usd eur", "1 btc to pln"]
+    }
 
-        let joiner = if target_currencies.len() <= 2 { " = " } else { "\n= " };
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(10, 30)]
+    }
 
-        let response = target_currencies
-            .into_iter()
-            .map(|target_currency| {
-                format!("{} {}", FormatAmount(target_currency.1), target_currency.0)
-            })
-            .collect::<Vec<_>>()
-            .join(joiner);
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let arguments = Arguments::convert(ctx, &arguments).await?.0;
+        let response = convert(&ctx.bot_state, ctx.language(), arguments).await?;
 
         ctx.reply(response).await?;
 
@@ -179,6 +141,86 @@ impl CommandTrait for Convert {
     }
 }
 
+#[async_trait]
+impl InlineCommandTrait for Convert {
+    fn inline_prefixes(&self) -> &[&str] {
+        &["c", "convert"]
+    }
+
+    async fn execute_inline(
+        &self,
+        ctx: &InlineQueryContext,
+        arguments: String,
+    ) -> InlineCommandResult {
+        let arguments = Arguments::parse(ctx.language(), &arguments)?;
+        let response = convert(&ctx.bot_state, ctx.language(), arguments).await?;
+
+        Ok(InlineArticle {
+            title: response.clone(),
+            description: String::new(),
+            text: FormattedText { text: response, ..Default::default() },
+        })
+    }
+}
+
+async fn convert(
+    bot_state: &BotState,
+    language: Language,
+    arguments: Arguments,
+) -> Result<String, CommandError> {
+    let fiat = bot_state
+        .response_cache
+        .get_or_fetch(Endpoint::Eurofxref, "", eurofxref::daily(&bot_state.http_client))
+        .await?;
+
+    let crypto = bot_state
+        .response_cache
+        .get_or_fetch(Endpoint::Coinranking, "", coinranking::coins(&bot_state.http_client))
+        .await?;
+
+    let (source_currency, amount_eur) = match get_fiat_rate(&fiat, &arguments.currency) {
+        Some((currency, rate)) => (currency, arguments.amount / rate),
+        None => match get_crypto_price(&crypto, &arguments.currency) {
+            Some((currency, price)) => (currency, arguments.amount * price),
+            None => {
+                return Err(CommandError::CustomFormattedText(message_entities::formatted_text(
+                    vec![
+                        i18n::text(language, "unknown_source_currency").text(),
+                        arguments.currency.code(),
+                    ],
+                )));
+            }
+        },
+    };
+
+    let target_currencies = iter::once(Ok((source_currency, arguments.amount)))
+        .chain(arguments.target_currencies.into_iter().map(|target_currency| {
+            match get_fiat_rate(&fiat, &target_currency) {
+                Some((symbol, rate)) => Ok((symbol, amount_eur * rate)),
+                None => match get_crypto_price(&crypto, &target_currency) {
+                    Some((symbol, price)) => Ok((symbol, amount_eur / price)),
+                    None => Err(CommandError::CustomFormattedText(
+                        message_entities::formatted_text(vec![
+                            i18n::text(language, "unknown_target_currency").text(),
+                            target_currency.code(),
+                        ]),
+                    )),
+                },
+            }
+        }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let joiner = if target_currencies.len() <= 2 { " = " } else { "\n= " };
+
+    let response = target_currencies
+        .into_iter()
+        .map(|target_currency| format!("{} {}", FormatAmount(target_currency.1), target_currency.0))
+        .collect::<Vec<_>>()
+        .join(joiner);
+
+    Ok(response)
+}
+
 fn get_fiat_rate<'a>(rates: &'a [Rate], currency: &str) -> Option<(&'a str, f64)> {
     if currency.eq_ignore_ascii_case("eur") {
         return Some(("EUR", 1.));
@@ -221,4 +263,30 @@ mod test {
         assert_eq!(format!("{}", FormatAmount(0.001234)), "0.00123");
         assert_eq!(format!("{}", FormatAmount(0.0001234)), "0.000123");
     }
+
+    #[test]
+    fn test_arguments() {
+        let arguments = Arguments::parse(Language::English, "btc").unwrap();
+        assert!((arguments.amount - 1.).abs() < f64::EPSILON);
+        assert_eq!(arguments.currency, "btc");
+        assert_eq!(arguments.target_currencies, ["usd"]);
+
+        let arguments = Arguments::parse(Language::English, "10 usd to pln eur").unwrap();
+        assert!((arguments.amount - 10.).abs() < f64::EPSILON);
+        assert_eq!(arguments.currency, "usd");
+        assert_eq!(arguments.target_currencies, ["pln", "eur"]);
+
+        assert!(matches!(
+            Arguments::parse(Language::English, ""),
+            Err(ConversionError::MissingArgument)
+        ));
+        assert!(matches!(
+            Arguments::parse(Language::English, "10"),
+            Err(ConversionError::MissingArgument)
+        ));
+        assert!(matches!(
+            Arguments::parse(Language::English, "nan usd"),
+            Err(ConversionError::BadArgument(_))
+        ));
+    }
 }
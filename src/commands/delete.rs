@@ -4,9 +4,7 @@ use tdlib::types::MessageReplyToMessage;
 
 use super::{CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
-
-#[expect(clippy::unreadable_literal)]
-const OWNER_ID: i64 = 807128293;
+use crate::utilities::settings::Role;
 
 pub struct Delete;
 
@@ -16,11 +14,11 @@ impl CommandTrait for Delete {
         &["delete", "del"]
     }
 
-    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
-        if ctx.user.id != OWNER_ID {
-            return Ok(());
-        }
+    fn required_role(&self) -> Role {
+        Role::Operator
+    }
 
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let Some(&MessageReplyTo::Message(MessageReplyToMessage { message_id, .. })) =
             ctx.message.reply_to.as_ref()
         else {
@@ -10,10 +10,11 @@ use tempfile::NamedTempFile;
 
 use super::{CommandResult, CommandTrait};
 use crate::apis::different_dimension_me;
+use crate::utilities::chat_action::Activity;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::file_download::MEBIBYTE;
 use crate::utilities::message_entities::ToEntity;
-use crate::utilities::{message_entities, telegram_utils};
+use crate::utilities::{i18n, message_entities, telegram_utils};
 
 pub struct DifferentDimensionMe;
 
@@ -25,20 +26,20 @@ impl CommandTrait for DifferentDimensionMe {
 
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let message_image =
-            telegram_utils::get_message_or_reply_attachment(&ctx.message, false, ctx.client_id)
+            telegram_utils::get_message_or_reply_attachment(&ctx.message, false, &*ctx.client)
                 .await?
-                .ok_or("send or reply to an image.")?;
+                .ok_or(i18n::text(ctx.language(), "image_required"))?;
 
         let file = message_image.file();
 
         if file.size > 4 * MEBIBYTE {
-            Err("the image cannot be larger than 4 MiB.")?;
+            Err(i18n::format(ctx.language(), "image_too_large", &[("size", &"4 MiB")]))?;
         }
 
         let File::File(file) =
             functions::download_file(file.id, 1, 0, 0, true, ctx.client_id).await?;
 
-        ctx.send_typing().await?;
+        ctx.send_chat_action(Activity::UploadingPhoto).await?;
 
         let result = different_dimension_me::process(
             ctx.bot_state.http_client.clone(),
@@ -48,16 +49,21 @@ impl CommandTrait for DifferentDimensionMe {
 
         let media = result.map_err(|err| {
             if err.message == "IMG_ILLEGAL" {
-                format!(
-                    "Xi Jinping does not approve of this image and has censored it (error {}: {})",
-                    err.code, err.message
+                i18n::format(
+                    ctx.language(),
+                    "image_censored",
+                    &[("code", &err.code), ("message", &err.message)],
                 )
             } else {
                 err.to_string()
             }
         })?;
 
-        let image_url = media.img_urls.into_iter().next().ok_or("the generation failed.")?;
+        let image_url = media
+            .img_urls
+            .into_iter()
+            .next()
+            .ok_or(i18n::text(ctx.language(), "generation_failed"))?;
         let response = ctx.bot_state.http_client.get(&image_url).send().await?;
         let image =
             image::load_from_memory_with_format(&response.bytes().await?, ImageFormat::Jpeg)
@@ -66,8 +72,9 @@ impl CommandTrait for DifferentDimensionMe {
         let mut temp_file = NamedTempFile::new().unwrap();
         image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
 
-        let formatted_text =
-            message_entities::formatted_text(vec!["open full image".text_url(image_url)]);
+        let formatted_text = message_entities::formatted_text(vec![
+            i18n::text(ctx.language(), "open_full_image").text_url(image_url),
+        ]);
 
         let message = ctx
             .reply_custom(
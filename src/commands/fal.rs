@@ -3,12 +3,14 @@ use tdlib::enums::{InputFile, InputMessageContent};
 use tdlib::types::{InputFileRemote, InputMessagePhoto};
 
 use crate::apis::fal;
-use crate::commands::{CommandError, CommandResult, CommandTrait};
+use crate::commands::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::chat_action::Activity;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::job_queue::ConcurrencyClass;
 use crate::utilities::message_entities::{ToEntity, ToEntityOwned, formatted_text};
-use crate::utilities::rate_limit::RateLimiter;
-use crate::utilities::text_utils;
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::{i18n, text_utils};
 
 pub struct Fal {
     command_names: &'static [&'static str],
@@ -44,19 +46,35 @@ impl CommandTrait for Fal {
         Some(self.description)
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(1, 120)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Images
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<prompt>")
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(1, 120), RateLimit::chat(20, 60 * 60)]
+    }
+
+    fn concurrency_class(&self) -> Option<ConcurrencyClass> {
+        Some(ConcurrencyClass::ImageGeneration)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        if let Some(issue) = text_utils::check_prompt(&prompt) {
+        if let Some(issue) = text_utils::check_prompt(ctx.language(), &prompt) {
             log::info!("prompt rejected: {issue:?}");
             return Err(CommandError::Custom(issue.into()));
         }
 
-        ctx.send_typing().await?;
+        ctx.send_chat_action(Activity::UploadingPhoto).await?;
         let response =
             fal::generate(ctx.bot_state.http_client.clone(), self.model_name, &prompt).await?;
         let image = response.images.into_iter().next().unwrap();
@@ -69,10 +87,15 @@ impl CommandTrait for Fal {
                 width: image.width.try_into().unwrap(),
                 height: image.height.try_into().unwrap(),
                 caption: Some(formatted_text(vec![
-                    "generated ".text(),
+                    i18n::text(ctx.language(), "generated").text(),
                     response.prompt.bold(),
-                    format!(" in {:.2}s. ", response.timings.inference).text_owned(),
-                    "download".text_url(image.url),
+                    i18n::format(
+                        ctx.language(),
+                        "generated_in_seconds",
+                        &[("seconds", &format_args!("{:.2}", response.timings.inference))],
+                    )
+                    .text_owned(),
+                    i18n::text(ctx.language(), "download").text_url(image.url),
                 ])),
                 show_caption_above_media: false,
                 self_destruct_type: None,
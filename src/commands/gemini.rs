@@ -9,14 +9,16 @@ use tdlib::{enums, functions};
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
 use crate::apis::google_aistudio::{
     self, Candidate, CitationSource, Content, FileData, GenerateContentResponse, Part, PartResponse,
 };
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, ReplyChain};
 use crate::utilities::file_download::MEBIBYTE;
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::settings::ModelSettings;
 use crate::utilities::telegram_utils;
 
 pub const SYSTEM_INSTRUCTION: &str = concat!(
@@ -28,24 +30,19 @@ pub const SYSTEM_INSTRUCTION: &str = concat!(
 pub struct Gemini {
     command_names: &'static [&'static str],
     description: &'static str,
-    model: &'static str,
+    model: String,
+    max_tokens: u16,
+    rate_limit: (u32, u32),
 }
 
 impl Gemini {
-    #[expect(clippy::self_named_constructors)]
-    pub const fn gemini() -> Self {
+    pub fn new(model: &ModelSettings) -> Self {
         Self {
-            command_names: &["gemini", "g", "gemini2", "g2"],
-            description: "ask Gemini 2.5 Flash",
-            model: "gemini-2.5-flash",
-        }
-    }
-
-    pub const fn gemini_lite() -> Self {
-        Self {
-            command_names: &["gemini_lite", "gl"],
-            description: "ask Gemini 2.5 Flash-Lite",
-            model: "gemini-2.5-flash-lite",
+            command_names: model.leak_command_names(),
+            description: model.leak_description(),
+            model: model.model.clone(),
+            max_tokens: model.max_tokens,
+            rate_limit: model.rate_limit,
         }
     }
 }
@@ -60,8 +57,20 @@ impl CommandTrait for Gemini {
         Some(self.description)
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(4, 60)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Ai
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<prompt>")
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(self.rate_limit.0, self.rate_limit.1)]
     }
 
     #[expect(clippy::too_many_lines)]
@@ -85,7 +94,12 @@ impl CommandTrait for Gemini {
                 let file = message_image.file();
 
                 if file.size > 64 * MEBIBYTE {
-                    return Err(CommandError::Custom("files cannot be larger than 64 MiB.".into()));
+                    return Err(i18n::format(
+                        ctx.language(),
+                        "file_too_large",
+                        &[("size", &"64 MiB")],
+                    )
+                    .into());
                 }
 
                 let File::File(file) =
@@ -113,7 +127,7 @@ impl CommandTrait for Gemini {
         }
 
         if contents.is_empty() {
-            return Err(CommandError::Custom("no prompt or file provided.".into()));
+            return Err(i18n::text(ctx.language(), "no_prompt_or_file").into());
         }
 
         let system_instruction = if contents
@@ -137,16 +151,17 @@ impl CommandTrait for Gemini {
 
         let http_client = ctx.bot_state.http_client.clone();
         let (tx, mut rx) = mpsc::unbounded_channel();
-        let model = self.model;
+        let model = self.model.clone();
+        let max_tokens = self.max_tokens;
 
         tokio::spawn(async move {
             google_aistudio::stream_generate_content(
                 http_client,
                 tx,
-                model,
+                &model,
                 Cow::Owned(contents),
                 system_instruction,
-                512,
+                max_tokens,
             )
             .await;
         });
@@ -171,7 +186,8 @@ impl CommandTrait for Gemini {
                             }
                             None => {
                                 if let Some(candidate) = response.candidates.into_iter().next() {
-                                    progress = Some(GenerationProgress::new(candidate));
+                                    progress =
+                                        Some(GenerationProgress::new(candidate, ctx.language()));
                                     changed_after_last_update = true;
                                 }
                             }
@@ -195,7 +211,10 @@ impl CommandTrait for Gemini {
                 };
 
                 let formatted_text = if text.trim().is_empty() {
-                    FormattedText { text: "[no text generated]".into(), ..Default::default() }
+                    FormattedText {
+                        text: i18n::text(ctx.language(), "no_text_generated_marker").into(),
+                        ..Default::default()
+                    }
                 } else {
                     let enums::FormattedText::FormattedText(formatted_text) =
                         functions::parse_markdown(
@@ -232,10 +251,11 @@ struct GenerationProgress {
     parts: Vec<PartResponse>,
     finish_reason: Option<String>,
     citation_sources: Vec<CitationSource>,
+    language: Language,
 }
 
 impl GenerationProgress {
-    fn new(candidate: Candidate) -> Self {
+    fn new(candidate: Candidate, language: Language) -> Self {
         Self {
             parts: candidate.content.and_then(|content| content.parts).unwrap_or_default(),
             finish_reason: candidate.finish_reason,
@@ -243,6 +263,7 @@ impl GenerationProgress {
                 .citation_metadata
                 .map(|citation_metadata| citation_metadata.citation_sources)
                 .unwrap_or_default(),
+            language,
         }
     }
 
@@ -260,14 +281,19 @@ impl GenerationProgress {
                     .collect::<Vec<_>>()
                     .join(", ");
 
-                return Err(format!("request blocked by Google: {reasons}.").into());
+                return Err(i18n::format(
+                    self.language,
+                    "gemini_blocked_reasons",
+                    &[("reasons", &reasons)],
+                )
+                .into());
             }
 
-            return Err(CommandError::Custom("request blocked by Google.".into()));
+            return Err(i18n::text(self.language, "gemini_blocked").into());
         }
 
         let Some(candidate) = response.candidates.into_iter().next() else {
-            return Err(CommandError::Custom("no response generated.".into()));
+            return Err(i18n::text(self.language, "no_response").into());
         };
 
         if let Some(content) = candidate.content {
@@ -292,7 +318,7 @@ impl GenerationProgress {
             .iter()
             .map(|part| match part {
                 PartResponse::Text(text) => text.as_str(),
-                PartResponse::InlineData => "[unsupported response part]",
+                PartResponse::InlineData => i18n::text(self.language, "unsupported_response_part"),
             })
             .collect::<Vec<_>>()
             .concat();
@@ -304,7 +330,9 @@ impl GenerationProgress {
         if let Some(finish_reason) = self.finish_reason.as_ref()
             && finish_reason != "STOP"
         {
-            write!(text, " [finish reason: {finish_reason}]").unwrap();
+            let finish_reason =
+                i18n::format(self.language, "finish_reason", &[("reason", finish_reason)]);
+            write!(text, " {finish_reason}").unwrap();
         }
 
         if !self.citation_sources.is_empty() {
@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::env;
 use std::fmt::Write;
 
 use async_trait::async_trait;
@@ -7,58 +6,33 @@ use tdlib::types::FormattedText;
 use tdlib::{enums, functions};
 
 use super::gemini::SYSTEM_INSTRUCTION;
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
 use crate::apis::openai::{self, Message};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, ReplyChain};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::settings::{self, ModelSettings};
 
 pub struct Groq {
     command_names: &'static [&'static str],
     description: &'static str,
-    model_name: &'static str,
+    model_name: String,
     max_tokens: u16,
-    thinking_markers: Option<(&'static str, &'static str)>,
+    rate_limit: (u32, u32),
+    thinking_markers: Option<(String, String)>,
 }
 
 impl Groq {
-    pub const fn gpt_oss() -> Self {
+    pub fn new(model: &ModelSettings) -> Self {
         Self {
-            command_names: &["gpt_oss", "gpt"],
-            description: "ask GPT OSS 120B",
-            model_name: "openai/gpt-oss-120b",
-            max_tokens: 1024,
-            thinking_markers: None,
-        }
-    }
-
-    pub const fn llama4() -> Self {
-        Self {
-            command_names: &["llama4", "llama"],
-            description: "ask Llama 4 Maverick",
-            model_name: "meta-llama/llama-4-maverick-17b-128e-instruct",
-            max_tokens: 512,
-            thinking_markers: None,
-        }
-    }
-
-    pub const fn llama3() -> Self {
-        Self {
-            command_names: &["llama3"],
-            description: "ask Llama 3.3 70B",
-            model_name: "llama-3.3-70b-versatile",
-            max_tokens: 512,
-            thinking_markers: None,
-        }
-    }
-
-    pub const fn kimi_k2() -> Self {
-        Self {
-            command_names: &["kimi_k2", "k2"],
-            description: "ask Kimi K2 0905",
-            model_name: "moonshotai/kimi-k2-instruct-0905",
-            max_tokens: 512,
-            thinking_markers: None,
+            command_names: model.leak_command_names(),
+            description: model.leak_description(),
+            model_name: model.model.clone(),
+            max_tokens: model.max_tokens,
+            rate_limit: model.rate_limit,
+            thinking_markers: model.thinking_markers.clone(),
         }
     }
 }
@@ -73,8 +47,20 @@ impl CommandTrait for Groq {
         Some(self.description)
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(4, 60)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Ai
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<prompt>")
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(self.rate_limit.0, self.rate_limit.1), RateLimit::api("groq", 30, 60)]
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
@@ -93,26 +79,30 @@ impl CommandTrait for Groq {
         }));
 
         if prompt_messages.len() <= 1 {
-            return Err(CommandError::Custom("no prompt provided.".into()));
+            return Err(i18n::text(ctx.language(), "no_prompt").into());
         }
 
         let response = openai::chat_completion(
             ctx.bot_state.http_client.clone(),
-            "https://api.groq.com/openai/v1",
-            &env::var("GROQ_API_KEY").unwrap(),
-            self.model_name,
+            Endpoint::Groq,
+            &settings::get().groq_api_key,
+            &self.model_name,
             self.max_tokens,
             &prompt_messages,
         )
         .await?
         .map_err(|err| {
-            CommandError::Custom(Cow::Owned(format!("error {}: {}", err.code, err.message)))
+            i18n::format(
+                ctx.language(),
+                "api_error",
+                &[("code", &err.code), ("message", &err.message)],
+            )
         })?;
 
         let choice = response.choices.into_iter().next().unwrap();
 
-        let mut text = if let Some((thinking_start, thinking_end)) = self.thinking_markers {
-            hide_thinking(choice.message.content, thinking_start, thinking_end)
+        let mut text = if let Some((thinking_start, thinking_end)) = &self.thinking_markers {
+            hide_thinking(ctx.language(), choice.message.content, thinking_start, thinking_end)
         } else {
             choice.message.content
         };
@@ -122,7 +112,10 @@ impl CommandTrait for Groq {
         }
 
         let formatted_text = if text.trim().is_empty() {
-            FormattedText { text: "[no text generated]".into(), ..Default::default() }
+            FormattedText {
+                text: i18n::text(ctx.language(), "no_text_generated_marker").into(),
+                ..Default::default()
+            }
         } else {
             let enums::FormattedText::FormattedText(formatted_text) = functions::parse_markdown(
                 FormattedText { text, ..Default::default() },
@@ -139,7 +132,12 @@ impl CommandTrait for Groq {
     }
 }
 
-fn hide_thinking(text: String, thinking_start: &str, thinking_end: &str) -> String {
+fn hide_thinking(
+    language: Language,
+    text: String,
+    thinking_start: &str,
+    thinking_end: &str,
+) -> String {
     let Some(stripped) = text.strip_prefix(thinking_start) else {
         return text;
     };
@@ -150,5 +148,7 @@ fn hide_thinking(text: String, thinking_start: &str, thinking_end: &str) -> Stri
 
     let stripped = stripped[index + thinking_end.len()..].trim_ascii_start();
 
-    format!("[{index} thinking chars hidden]\n{stripped}")
+    let hidden = i18n::format(language, "thinking_hidden", &[("count", &index)]);
+
+    format!("{hidden}\n{stripped}")
 }
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::command_manager::CommandInstance;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::message_entities::{self, Entity, ToEntity, ToEntityOwned};
+use crate::utilities::settings;
+
+pub struct Help;
+
+#[async_trait]
+impl CommandTrait for Help {
+    fn command_names(&self) -> &[&str] {
+        &["help", "commands"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("list commands or show how to use one")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("[command]")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["", "translate"]
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let commands = visible_commands(ctx);
+        let language = ctx.language();
+
+        let entities = match Option::<String>::convert(ctx, &arguments).await?.0 {
+            Some(name) => {
+                let name = name.trim_start_matches('/').to_ascii_lowercase();
+
+                let command = commands
+                    .iter()
+                    .find(|command| command.command.command_names().contains(&name.as_str()))
+                    .ok_or_else(|| i18n::text(language, "unknown_command"))?;
+
+                command_help(command, language)
+            }
+            None => command_list(&commands, language),
+        };
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
+
+/// commands with a description which the user can run in the chat
+fn visible_commands(ctx: &CommandContext) -> Vec<Arc<CommandInstance>> {
+    let role = settings::get().role(ctx.user.id);
+    let commands = ctx.bot_state.command_manager.lock().unwrap().commands().to_vec();
+    let config = ctx.bot_state.config.lock().unwrap();
+
+    commands
+        .into_iter()
+        .filter(|command| {
+            command.command.description().is_some()
+                && role >= command.command.required_role()
+                && !config.is_command_disabled(ctx.message.chat_id, command.name())
+        })
+        .collect()
+}
+
+fn command_list(commands: &[Arc<CommandInstance>], language: Language) -> Vec<Entity<'_>> {
+    let mut categories = BTreeMap::<_, Vec<_>>::new();
+
+    for command in commands {
+        categories.entry(command.command.category()).or_default().push(command);
+    }
+
+    let mut entities = Vec::new();
+
+    for (category, commands) in categories {
+        if !entities.is_empty() {
+            entities.push("\n\n".text());
+        }
+
+        entities.push(category.name(language).bold());
+
+        for command in commands {
+            entities.push("\n/".text());
+            entities.push(command.name().text());
+            entities.push(" – ".text());
+            entities.push(description(command, language).unwrap().text());
+        }
+    }
+
+    let (before, after) = i18n::text(language, "help_usage_hint").split_once("{command}").unwrap();
+
+    entities.push(format!("\n\n{before}").text_owned());
+    entities.push("/help <command>".code());
+    entities.push(after.text());
+
+    entities
+}
+
+/// the translated description of the command, or the one it was registered with
+fn description(command: &CommandInstance, language: Language) -> Option<&'static str> {
+    i18n::command_description(language, command.name()).or_else(|| command.command.description())
+}
+
+fn command_help(command: &CommandInstance, language: Language) -> Vec<Entity<'_>> {
+    let name = command.name();
+
+    let mut entities = vec![
+        command
+            .command
+            .usage()
+            .map_or_else(|| format!("/{name}"), |usage| format!("/{name} {usage}"))
+            .code_owned(),
+    ];
+
+    if let Some(description) = description(command, language) {
+        entities.push("\n".text());
+        entities.push(description.text());
+    }
+
+    let aliases = &command.command.command_names()[1..];
+
+    if !aliases.is_empty() {
+        entities.push(format!("\n\n{}", i18n::text(language, "help_aliases")).text_owned());
+        entities.extend(
+            aliases
+                .iter()
+                .flat_map(|alias| [", ".text(), format!("/{alias}").code_owned()])
+                .skip(1),
+        );
+    }
+
+    if !command.command.arguments().is_empty() {
+        entities.push(format!("\n\n{}", i18n::text(language, "help_arguments")).text_owned());
+
+        for (argument, description) in command.command.arguments() {
+            entities.push("\n".text());
+            entities.push(argument.code());
+            entities.push(" – ".text());
+            entities.push(description.text());
+        }
+    }
+
+    if !command.command.examples().is_empty() {
+        entities.push(format!("\n\n{}", i18n::text(language, "help_examples")).text_owned());
+
+        for example in command.command.examples() {
+            entities.push("\n".text());
+            entities.push(
+                if example.is_empty() { format!("/{name}") } else { format!("/{name} {example}") }
+                    .code_owned(),
+            );
+        }
+    }
+
+    entities
+}
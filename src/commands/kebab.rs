@@ -1,9 +1,13 @@
 use async_trait::async_trait;
 use rand::seq::IndexedRandom;
+use tdlib::enums::{InputMessageContent, ReplyMarkup};
+use tdlib::types::{FormattedText, InputMessageText, ReplyMarkupInlineKeyboard};
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
+use crate::utilities::callback_context::CallbackContext;
 use crate::utilities::command_context::CommandContext;
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::telegram_utils;
 
 const WORDS: [&str; 7] = ["kebab", "king", "house", "super", "arab", "hot", "sauce"];
 
@@ -19,16 +23,40 @@ impl CommandTrait for Kebab {
         Some("generates a generic kebab shop name")
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(10, 30)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Fun
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(10, 30)]
     }
 
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
-        let random_name =
-            WORDS.choose_multiple(&mut rand::rng(), 2).copied().collect::<Vec<&str>>().join(" ");
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: FormattedText { text: random_name(), ..Default::default() },
+                ..Default::default()
+            }),
+            Some(reroll_markup()),
+        )
+        .await?;
+
+        Ok(())
+    }
 
-        ctx.reply(random_name).await?;
+    async fn callback(&self, ctx: &CallbackContext, _: String) -> CommandResult {
+        ctx.edit_message(random_name(), Some(reroll_markup())).await?;
 
         Ok(())
     }
 }
+
+fn random_name() -> String {
+    WORDS.choose_multiple(&mut rand::rng(), 2).copied().collect::<Vec<&str>>().join(" ")
+}
+
+fn reroll_markup() -> ReplyMarkup {
+    ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard {
+        rows: vec![vec![telegram_utils::callback_button("🔄", "kebab", "reroll")]],
+    })
+}
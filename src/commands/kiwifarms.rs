@@ -3,9 +3,10 @@ use std::error::Error;
 use async_trait::async_trait;
 use reqwest::StatusCode;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::kiwifarms;
 use crate::utilities::command_context::CommandContext;
+use crate::utilities::i18n;
 
 pub struct KiwiFarms;
 
@@ -19,20 +20,29 @@ impl CommandTrait for KiwiFarms {
         Some("checks if The Kiwi Farms forum still works")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Fun
+    }
+
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         ctx.send_typing().await?;
 
         let text = match kiwifarms::status(ctx.bot_state.http_client.clone()).await {
             Ok(status) => {
                 if status == StatusCode::NON_AUTHORITATIVE_INFORMATION {
-                    "yes 🤬".into()
+                    i18n::text(ctx.language(), "kiwifarms_yes").into()
                 } else {
-                    format!("{} no", status.as_u16())
+                    i18n::format(
+                        ctx.language(),
+                        "kiwifarms_status",
+                        &[("status", &status.as_u16())],
+                    )
                 }
             }
             Err(err) => {
                 let err = err.without_url();
-                format!("no ({})", err.source().map_or(&err as &dyn Error, |err| err))
+                let source = err.source().map_or(&err as &dyn Error, |err| err);
+                i18n::format(ctx.language(), "kiwifarms_error", &[("error", &source)])
             }
         };
         ctx.reply(text).await?;
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use tdlib::functions;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::settings::Role;
+
+pub struct Leave;
+
+#[async_trait]
+impl CommandTrait for Leave {
+    fn command_names(&self) -> &[&str] {
+        &["leave"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let arguments = arguments.trim();
+
+        let chat_id = if arguments.is_empty() {
+            ctx.message.chat_id
+        } else {
+            arguments.parse().map_err(|_| "invalid chat ID.")?
+        };
+
+        let chat = ctx.bot_state.cache.lock().unwrap().get_chat(chat_id);
+        let Some(chat) = chat else {
+            return Err("unknown chat.".into());
+        };
+
+        functions::leave_chat(chat_id, ctx.client_id).await?;
+
+        if chat_id != ctx.message.chat_id {
+            ctx.reply(format!("left {chat}.")).await?;
+        }
+
+        Ok(())
+    }
+}
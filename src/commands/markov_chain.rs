@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 use tdlib::enums::ChatType;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
 use crate::utilities::command_context::CommandContext;
+use crate::utilities::i18n;
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::RateLimit;
 
 pub struct MarkovChain;
 
@@ -18,8 +19,16 @@ impl CommandTrait for MarkovChain {
         Some("generate text based on seen chat messages")
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 20)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Fun
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(3, 20)]
     }
 
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
@@ -33,7 +42,7 @@ impl CommandTrait for MarkovChain {
                 .contains(&ctx.message.chat_id)
         {
             ctx.reply_formatted_text(message_entities::formatted_text(vec![
-                "a chat admin has to enable Markov chain learning with ".text(),
+                i18n::text(ctx.language(), "markov_learning_required").text(),
                 "/config markov_chain_learning true".code(),
                 ".".text(),
             ]))
@@ -42,7 +51,8 @@ impl CommandTrait for MarkovChain {
         }
 
         let text = ctx.bot_state.markov_chain.lock().unwrap().generate_text(64);
-        ctx.reply(text.unwrap_or_else(|| "no text was generated.".into())).await?;
+        ctx.reply(text.unwrap_or_else(|| i18n::text(ctx.language(), "no_text_generated").into()))
+            .await?;
 
         Ok(())
     }
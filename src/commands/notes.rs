@@ -0,0 +1,350 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{
+    FormattedText, InputFileRemote, InputMessageAnimation, InputMessageAudio, InputMessageDocument,
+    InputMessagePhoto, InputMessageSticker, InputMessageVideo, InputMessageVideoNote,
+    InputMessageVoiceNote,
+};
+
+use super::config::group_admin_guard;
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConversionError, ConvertArgument};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::notes::{AttachmentKind, Note, NoteAttachment};
+use crate::utilities::{i18n, telegram_utils};
+
+const MAX_NOTES: usize = 100;
+const MAX_NAME_LENGTH: usize = 32;
+
+pub struct Save;
+
+#[async_trait]
+impl CommandTrait for Save {
+    fn command_names(&self) -> &[&str] {
+        &["save"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("save a note in this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats, MenuScope::ChatAdministrators]
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<name> [text]")
+    }
+
+    fn arguments(&self) -> &[(&'static str, &'static str)] {
+        &[("text", "content of the note, or the replied message if omitted")]
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["rules be nice"]
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        group_admin_guard(ctx).await?;
+
+        let (name, text) = note_name(ctx, &arguments).await?;
+        let text = text.trim_ascii();
+
+        let note = if text.is_empty() {
+            replied_note(ctx).await?
+        } else {
+            Note {
+                text: FormattedText { text: text.into(), ..Default::default() },
+                attachment: None,
+            }
+        };
+
+        let replaced = {
+            let mut notes = ctx.bot_state.notes.lock().unwrap();
+
+            if notes.get(ctx.message.chat_id, &name).is_none()
+                && notes.count(ctx.message.chat_id) >= MAX_NOTES
+            {
+                return Err(
+                    i18n::format(ctx.language(), "note_limit", &[("limit", &MAX_NOTES)]).into()
+                );
+            }
+
+            notes.insert(ctx.message.chat_id, name.clone(), note)
+        };
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            i18n::text(ctx.language(), "note").text(),
+            name.code_owned(),
+            if replaced {
+                i18n::text(ctx.language(), "note_replaced")
+            } else {
+                i18n::text(ctx.language(), "note_saved")
+            }
+            .text(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub struct Get;
+
+#[async_trait]
+impl CommandTrait for Get {
+    fn command_names(&self) -> &[&str] {
+        &["get"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show a note saved in this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<name>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let name = note_name(ctx, &arguments).await?.0;
+
+        let note = ctx
+            .bot_state
+            .notes
+            .lock()
+            .unwrap()
+            .get(ctx.message.chat_id, &name)
+            .cloned()
+            .ok_or(i18n::text(ctx.language(), "no_such_note"))?;
+
+        match note.attachment {
+            Some(attachment) => {
+                ctx.reply_custom(attachment_content(attachment, note.text), None).await?;
+            }
+            None => {
+                ctx.reply_formatted_text(note.text).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Notes;
+
+#[async_trait]
+impl CommandTrait for Notes {
+    fn command_names(&self) -> &[&str] {
+        &["notes"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("list notes saved in this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let names = ctx.bot_state.notes.lock().unwrap().names(ctx.message.chat_id);
+
+        if names.is_empty() {
+            return Err(i18n::text(ctx.language(), "no_notes").into());
+        }
+
+        let mut entities = vec![i18n::text(ctx.language(), "note_list").text()];
+
+        for name in names {
+            entities.push("\n".text());
+            entities.push(name.code_owned());
+        }
+
+        entities.push(i18n::text(ctx.language(), "note_list_hint").text());
+        entities.push("/get <name>".code());
+        entities.push(".".text());
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
+
+pub struct Clear;
+
+#[async_trait]
+impl CommandTrait for Clear {
+    fn command_names(&self) -> &[&str] {
+        &["clear"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("remove a note from this chat")
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &[MenuScope::PrivateChats, MenuScope::ChatAdministrators]
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<name>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        group_admin_guard(ctx).await?;
+
+        let name = note_name(ctx, &arguments).await?.0;
+
+        if ctx.bot_state.notes.lock().unwrap().remove(ctx.message.chat_id, &name).is_none() {
+            return Err(i18n::text(ctx.language(), "no_such_note").into());
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            i18n::text(ctx.language(), "note").text(),
+            name.code_owned(),
+            i18n::text(ctx.language(), "note_removed").text(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
+
+async fn note_name<'a>(
+    ctx: &CommandContext,
+    arguments: &'a str,
+) -> Result<(String, &'a str), CommandError> {
+    let (mut name, rest) = String::convert(ctx, arguments).await?;
+    name.make_ascii_lowercase();
+
+    if name.len() > MAX_NAME_LENGTH {
+        Err(ConversionError::BadArgument(Cow::Owned(i18n::format(
+            ctx.language(),
+            "note_name_too_long",
+            &[("limit", &MAX_NAME_LENGTH)],
+        ))))?;
+    }
+
+    Ok((name, rest))
+}
+
+async fn replied_note(ctx: &CommandContext) -> Result<Note, CommandError> {
+    if ctx.message.reply_to.is_none() {
+        return Err(i18n::text(ctx.language(), "note_missing_text").into());
+    }
+
+    let message = ctx.client.get_replied_message(ctx.message.chat_id, ctx.message.id).await?;
+
+    let text = telegram_utils::get_message_text(&message.content).cloned().unwrap_or_default();
+    let attachment = telegram_utils::get_message_attachment(Cow::Borrowed(&message.content), true)
+        .map(|attachment| NoteAttachment::from(&attachment));
+
+    if text.text.is_empty() && attachment.is_none() {
+        return Err(i18n::text(ctx.language(), "note_unsupported_message").into());
+    }
+
+    Ok(Note { text, attachment })
+}
+
+fn attachment_content(attachment: NoteAttachment, caption: FormattedText) -> InputMessageContent {
+    let file = InputFile::Remote(InputFileRemote { id: attachment.file_id });
+    let caption = Some(caption).filter(|caption| !caption.text.is_empty());
+
+    match attachment.kind {
+        AttachmentKind::Animation => {
+            InputMessageContent::InputMessageAnimation(InputMessageAnimation {
+                animation: file,
+                thumbnail: None,
+                added_sticker_file_ids: Vec::new(),
+                duration: 0,
+                width: 0,
+                height: 0,
+                caption,
+                show_caption_above_media: false,
+                has_spoiler: false,
+            })
+        }
+        AttachmentKind::Audio => InputMessageContent::InputMessageAudio(InputMessageAudio {
+            audio: file,
+            album_cover_thumbnail: None,
+            duration: 0,
+            title: String::new(),
+            performer: String::new(),
+            caption,
+        }),
+        AttachmentKind::Document => {
+            InputMessageContent::InputMessageDocument(InputMessageDocument {
+                document: file,
+                thumbnail: None,
+                disable_content_type_detection: true,
+                caption,
+            })
+        }
+        AttachmentKind::Photo => InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+            photo: file,
+            thumbnail: None,
+            added_sticker_file_ids: Vec::new(),
+            width: 0,
+            height: 0,
+            caption,
+            show_caption_above_media: false,
+            self_destruct_type: None,
+            has_spoiler: false,
+        }),
+        AttachmentKind::Sticker => InputMessageContent::InputMessageSticker(InputMessageSticker {
+            sticker: file,
+            thumbnail: None,
+            width: 0,
+            height: 0,
+            emoji: String::new(),
+        }),
+        AttachmentKind::Video => InputMessageContent::InputMessageVideo(InputMessageVideo {
+            video: file,
+            thumbnail: None,
+            cover: None,
+            start_timestamp: 0,
+            added_sticker_file_ids: Vec::new(),
+            duration: 0,
+            width: 0,
+            height: 0,
+            supports_streaming: true,
+            caption,
+            show_caption_above_media: false,
+            self_destruct_type: None,
+            has_spoiler: false,
+        }),
+        AttachmentKind::VideoNote => {
+            InputMessageContent::InputMessageVideoNote(InputMessageVideoNote {
+                video_note: file,
+                thumbnail: None,
+                duration: 0,
+                length: 0,
+                self_destruct_type: None,
+            })
+        }
+        AttachmentKind::VoiceNote => {
+            InputMessageContent::InputMessageVoiceNote(InputMessageVoiceNote {
+                voice_note: file,
+                duration: 0,
+                waveform: String::new(),
+                caption,
+                self_destruct_type: None,
+            })
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::env;
 use std::fmt::Write;
 
 use async_trait::async_trait;
@@ -7,35 +6,31 @@ use tdlib::types::FormattedText;
 use tdlib::{enums, functions};
 
 use super::gemini::SYSTEM_INSTRUCTION;
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
 use crate::apis::openai::{self, Message};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, ReplyChain};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n;
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::settings::{self, ModelSettings};
 
 pub struct OpenRouter {
     command_names: &'static [&'static str],
     description: &'static str,
-    model: &'static str,
-    rate_limit: (usize, i32),
+    model: String,
+    max_tokens: u16,
+    rate_limit: (u32, u32),
 }
 
 impl OpenRouter {
-    pub const fn mistral() -> Self {
+    pub fn new(model: &ModelSettings) -> Self {
         Self {
-            command_names: &["mistral"],
-            description: "ask Mistral Small 3",
-            model: "mistralai/mistral-small-24b-instruct-2501",
-            rate_limit: (6, 60),
-        }
-    }
-
-    pub const fn perplexity() -> Self {
-        Self {
-            command_names: &["perplexity", "sonar"],
-            description: "ask Perplexity Sonar",
-            model: "perplexity/sonar",
-            rate_limit: (2, 120),
+            command_names: model.leak_command_names(),
+            description: model.leak_description(),
+            model: model.model.clone(),
+            max_tokens: model.max_tokens,
+            rate_limit: model.rate_limit,
         }
     }
 }
@@ -50,8 +45,20 @@ impl CommandTrait for OpenRouter {
         Some(self.description)
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(self.rate_limit.0, self.rate_limit.1)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Ai
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<prompt>")
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(self.rate_limit.0, self.rate_limit.1)]
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
@@ -70,20 +77,24 @@ impl CommandTrait for OpenRouter {
         }));
 
         if prompt_messages.len() <= 1 {
-            return Err(CommandError::Custom("no prompt provided.".into()));
+            return Err(i18n::text(ctx.language(), "no_prompt").into());
         }
 
         let response = openai::chat_completion(
             ctx.bot_state.http_client.clone(),
-            "https://openrouter.ai/api/v1",
-            &env::var("OPENROUTER_API_KEY").unwrap(),
-            self.model,
-            512,
+            Endpoint::OpenRouter,
+            &settings::get().openrouter_api_key,
+            &self.model,
+            self.max_tokens,
             &prompt_messages,
         )
         .await?
         .map_err(|err| {
-            CommandError::Custom(Cow::Owned(format!("error {}: {}", err.code, err.message)))
+            i18n::format(
+                ctx.language(),
+                "api_error",
+                &[("code", &err.code), ("message", &err.message)],
+            )
         })?;
 
         let choice = response.choices.into_iter().next().unwrap();
@@ -94,7 +105,10 @@ impl CommandTrait for OpenRouter {
         }
 
         let formatted_text = if text.trim().is_empty() {
-            FormattedText { text: "[no text generated]".into(), ..Default::default() }
+            FormattedText {
+                text: i18n::text(ctx.language(), "no_text_generated_marker").into(),
+                ..Default::default()
+            }
         } else {
             let enums::FormattedText::FormattedText(formatted_text) = functions::parse_markdown(
                 FormattedText { text, ..Default::default() },
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::petittube;
 use crate::utilities::command_context::CommandContext;
 
@@ -16,6 +16,10 @@ impl CommandTrait for Petittube {
         Some("get a random YouTube video with almost no views")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Media
+    }
+
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let identifier = petittube::random_video(&ctx.bot_state.http_client).await?;
         ctx.reply_webpage(format!("https://youtu.be/{identifier}")).await?;
@@ -3,7 +3,7 @@ use std::time::Instant;
 use async_trait::async_trait;
 use tdlib::functions;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
 
 pub struct Ping;
@@ -18,6 +18,10 @@ impl CommandTrait for Ping {
         Some("check if the bot is online")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Bot
+    }
+
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let start = Instant::now();
         functions::ping_proxy(0, ctx.client_id).await?;
@@ -1,10 +1,14 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use time::macros;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::polymarket;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n;
 use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned, ToNestedEntity};
 
 pub struct Polymarket;
@@ -19,15 +23,35 @@ impl CommandTrait for Polymarket {
         Some("check Polymarket bets on world events")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<query>")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["election"]
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(query) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
         ctx.send_typing().await?;
 
-        let events = polymarket::search_events(&ctx.bot_state.http_client, &query).await?;
-
-        let Some(events) = events else {
-            return Err(CommandError::Custom("no results found.".into()));
+        let events = ctx
+            .bot_state
+            .response_cache
+            .get_or_fetch(
+                Endpoint::Polymarket,
+                query.as_str(),
+                polymarket::search_events(&ctx.bot_state.http_client, &query),
+            )
+            .await?;
+
+        let Some(events) = Arc::unwrap_or_clone(events) else {
+            return Err(i18n::text(ctx.language(), "no_results").into());
         };
 
         let mut event = events.into_iter().next().unwrap();
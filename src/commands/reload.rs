@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::settings::{self, Role};
+
+pub struct Reload;
+
+#[async_trait]
+impl CommandTrait for Reload {
+    fn command_names(&self) -> &[&str] {
+        &["reload"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        settings::reload().map_err(|err| err.to_string())?;
+        ctx.reply("settings reloaded.".into()).await?;
+
+        Ok(())
+    }
+}
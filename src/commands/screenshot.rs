@@ -8,12 +8,13 @@ use tempfile::NamedTempFile;
 use url::ParseError;
 
 use super::CommandError::CustomFormattedText;
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::microlink;
+use crate::utilities::chat_action::Activity;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::RateLimit;
 
 pub struct Screenshot;
 
@@ -27,8 +28,20 @@ impl CommandTrait for Screenshot {
         Some("screenshot a webpage")
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 60)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Media
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<url>")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["example.com"]
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(3, 60)]
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
@@ -39,7 +52,7 @@ impl CommandTrait for Screenshot {
             url => url,
         };
 
-        ctx.send_typing().await?;
+        ctx.send_chat_action(Activity::UploadingPhoto).await?;
 
         let data = microlink::screenshot(
             ctx.bot_state.http_client.clone(),
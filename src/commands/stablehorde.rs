@@ -12,12 +12,16 @@ use tdlib::types::{
 };
 use tempfile::NamedTempFile;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{CommandCategory, CommandError, CommandResult, CommandTrait, MenuScope};
 use crate::apis::stablehorde::{self, GeneratedImage, Status};
+use crate::utilities::chat_action::Activity;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::i18n::{self, Language};
+use crate::utilities::job_queue::ConcurrencyClass;
 use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned, formatted_text};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::RateLimit;
+use crate::utilities::running_commands::Cleanup;
 use crate::utilities::text_utils::TruncateWithEllipsis;
 use crate::utilities::{api_utils, image_utils, text_utils};
 
@@ -76,20 +80,35 @@ impl CommandTrait for StableHorde {
         Some(self.description)
     }
 
-    fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 300)
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Images
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<prompt>")
+    }
+
+    fn rate_limits(&self) -> Vec<RateLimit> {
+        vec![RateLimit::user(3, 300), RateLimit::chat(20, 60 * 60)]
+    }
+
+    fn concurrency_class(&self) -> Option<ConcurrencyClass> {
+        Some(ConcurrencyClass::ImageGeneration)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        if let Some(issue) = text_utils::check_prompt(&prompt) {
+        if let Some(issue) = text_utils::check_prompt(ctx.language(), &prompt) {
             log::info!("prompt rejected: {issue:?}");
             return Err(CommandError::Custom(issue.into()));
         }
 
-        ctx.send_typing().await?;
-
+        let chat_action = ctx.keep_chat_action(Activity::UploadingPhoto);
         let generation = Box::pin(self.generate(ctx, prompt)).await?;
         let images = download_images(ctx.bot_state.http_client.clone(), &generation.urls).await?;
         let image = process_images(images, self.size);
@@ -108,14 +127,14 @@ impl CommandTrait for StableHorde {
                     added_sticker_file_ids: Vec::new(),
                     width: image.width().try_into().unwrap(),
                     height: image.height().try_into().unwrap(),
-                    caption: Some(format_result_text(generation)),
+                    caption: Some(format_result_text(ctx.language(), generation)),
                     show_caption_above_media: false,
                     self_destruct_type: None,
                     has_spoiler: false,
                 }),
                 Some(ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard {
                     rows: vec![vec![InlineKeyboardButton {
-                        text: "generated thanks to Stable Horde".into(),
+                        text: i18n::text(ctx.language(), "stablehorde_credit").into(),
                         r#type: InlineKeyboardButtonType::Url(InlineKeyboardButtonTypeUrl {
                             url: "https://stablehorde.net/".into(),
                         }),
@@ -125,6 +144,8 @@ impl CommandTrait for StableHorde {
             .await?;
 
         ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        drop(chat_action);
+
         if let Some(status_msg_id) = status_msg_id {
             ctx.delete_message(status_msg_id).await.ok();
         }
@@ -155,6 +176,7 @@ impl StableHorde {
             self.size,
         )
         .await??;
+        ctx.add_cleanup(Cleanup::StableHordeGeneration(request_id.clone()));
         let escaped_prompt = prompt.truncate_with_ellipsis(256);
         let (results, status_msg_id, time_taken) =
             Box::pin(wait_for_generation(ctx, &request_id, &escaped_prompt)).await?;
@@ -178,7 +200,7 @@ impl StableHorde {
             .collect::<Vec<_>>();
 
         if urls.is_empty() {
-            Err("no images were successfully generated.")?;
+            Err(i18n::text(ctx.language(), "no_images_generated"))?;
         }
 
         Ok(Generation { urls, time_taken, escaped_prompt, workers, status_msg_id })
@@ -204,12 +226,12 @@ async fn wait_for_generation(
         }
 
         if status.faulted {
-            Err("the generation timed out.")?;
+            Err(i18n::text(ctx.language(), "generation_timed_out"))?;
         }
 
         if !status.is_possible {
             stablehorde::cancel_generation(ctx.bot_state.http_client.clone(), request_id).await?;
-            Err("there are no online workers for the requested model.")?;
+            Err(i18n::text(ctx.language(), "no_online_workers"))?;
         }
 
         if status.wait_time >= 60 {
@@ -219,15 +241,23 @@ async fn wait_for_generation(
         if last_status.as_ref() != Some(&status) {
             // the message doesn't exist yet or was edited more than 12 seconds ago
             if last_edit.is_none_or(|last_edit| last_edit.elapsed() >= Duration::from_secs(12)) {
-                let formatted_text =
-                    format_status_text(&status, escaped_prompt, show_volunteer_notice);
+                let formatted_text = format_status_text(
+                    ctx.language(),
+                    &status,
+                    escaped_prompt,
+                    show_volunteer_notice,
+                );
                 status_msg_id = Some(match status_msg_id {
                     None => {
-                        ctx.bot_state
+                        let status_msg_id = ctx
+                            .bot_state
                             .message_queue
                             .wait_for_message(ctx.reply_formatted_text(formatted_text).await?.id)
                             .await?
-                            .id
+                            .id;
+
+                        ctx.add_cleanup(Cleanup::DeleteMessage(status_msg_id));
+                        status_msg_id
                     }
                     Some(status_msg) => {
                         ctx.edit_message_formatted_text(status_msg, formatted_text).await?.id
@@ -270,18 +300,24 @@ fn process_images(images: Vec<Vec<u8>>, size: (u32, u32)) -> DynamicImage {
 }
 
 fn format_status_text(
+    language: Language,
     status: &Status,
     escaped_prompt: &str,
     volunteer_notice: bool,
 ) -> FormattedText {
     let queue_info = if status.queue_position > 0 {
-        format!("queue position: {}\n", status.queue_position)
+        let queue_position = i18n::format(
+            language,
+            "stablehorde_queue_position",
+            &[("position", &status.queue_position)],
+        );
+        format!("{queue_position}\n")
     } else {
         String::new()
     };
 
     let mut entities = vec![
-        "generating ".text(),
+        i18n::text(language, "generating").text(),
         escaped_prompt.text(),
         "…\n".text(),
         queue_info.text(),
@@ -297,8 +333,9 @@ fn format_status_text(
 
     if volunteer_notice {
         entities.extend([
-            "\n\nStable Horde is run by volunteers. to make wait times shorter, ".text(),
-            "consider joining yourself".text_url("https://stablehorde.net/"),
+            "\n\n".text(),
+            i18n::text(language, "stablehorde_volunteers").text(),
+            i18n::text(language, "stablehorde_join").text_url("https://stablehorde.net/"),
             "!".text(),
         ]);
     }
@@ -306,7 +343,7 @@ fn format_status_text(
     message_entities::formatted_text(entities)
 }
 
-fn format_result_text(generation: Generation) -> FormattedText {
+fn format_result_text(language: Language, generation: Generation) -> FormattedText {
     let workers = generation
         .workers
         .most_common()
@@ -337,15 +374,15 @@ fn format_result_text(generation: Generation) -> FormattedText {
         .collect::<Vec<_>>();
 
     let mut entities = vec![
-        "generated ".text(),
+        i18n::text(language, "generated").text(),
         generation.escaped_prompt.bold(),
-        " in ".text(),
+        i18n::text(language, "generated_in").text(),
         text_utils::format_duration(generation.time_taken.as_secs()).text_owned(),
-        " by ".text(),
+        i18n::text(language, "generated_by").text(),
     ];
 
     entities.extend(workers);
-    entities.push(".\ndownload: ".text());
+    entities.push(i18n::text(language, "download_links").text());
     entities.extend(download_urls);
 
     formatted_text(entities)
@@ -0,0 +1,120 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::settings::Role;
+use crate::utilities::stats::CommandStats;
+
+const TOP_COMMANDS: usize = 5;
+
+pub struct Stats;
+
+#[async_trait]
+impl CommandTrait for Stats {
+    fn command_names(&self) -> &[&str] {
+        &["stats"]
+    }
+
+    fn required_role(&self) -> Role {
+        Role::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        let text = {
+            let stats = ctx.bot_state.stats.lock().unwrap();
+
+            [
+                ("last 24h", &stats.since(now - 24 * 60 * 60)),
+                ("last 7d", &stats.since(now - 7 * 24 * 60 * 60)),
+                ("all time", stats.all_time()),
+            ]
+            .into_iter()
+            .map(|(name, commands)| format_stats(name, commands))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+        };
+
+        ctx.reply(text).await?;
+
+        Ok(())
+    }
+}
+
+fn format_stats(name: &str, commands: &HashMap<String, CommandStats>) -> String {
+    let invocations = commands.values().map(|stats| stats.invocations).sum::<u64>();
+    let failures = commands.values().map(CommandStats::failures).sum::<u64>();
+    let users = commands.values().flat_map(|stats| &stats.users).collect::<HashSet<_>>().len();
+
+    let mut text = format!(
+        "{name}: {}, {}% failed",
+        format_runs(invocations, users),
+        percentage(failures, invocations)
+    );
+
+    let mut commands = commands.iter().collect::<Vec<_>>();
+    commands.sort_unstable_by(|(a_name, a), (b_name, b)| {
+        b.invocations.cmp(&a.invocations).then_with(|| a_name.cmp(b_name))
+    });
+
+    for (command, stats) in commands.into_iter().take(TOP_COMMANDS) {
+        write!(
+            text,
+            "\n/{command}: {}, {}% failed",
+            format_runs(stats.invocations, stats.users.len()),
+            percentage(stats.failures(), stats.invocations)
+        )
+        .unwrap();
+
+        if let (Some(p50), Some(p95)) = (stats.latency_percentile(50), stats.latency_percentile(95))
+        {
+            write!(text, ", p50 {}, p95 {}", format_latency(p50), format_latency(p95)).unwrap();
+        }
+
+        let mut errors = stats.errors.iter().collect::<Vec<_>>();
+        errors
+            .sort_unstable_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+
+        if !errors.is_empty() {
+            let errors = errors
+                .into_iter()
+                .map(|(error, count)| format!("{error} ×{count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            write!(text, " ({errors})").unwrap();
+        }
+    }
+
+    text
+}
+
+/// users aren't counted in all-time stats
+fn format_runs(invocations: u64, users: usize) -> String {
+    if users == 0 {
+        format!("{invocations} runs")
+    } else {
+        format!("{invocations} runs by {users} users")
+    }
+}
+
+fn percentage(part: u64, total: u64) -> u64 {
+    if total == 0 { 0 } else { part * 100 / total }
+}
+
+fn format_latency(milliseconds: u32) -> String {
+    if milliseconds == u32::MAX {
+        "> 5m".into()
+    } else if milliseconds < 1000 {
+        format!("≤ {milliseconds}ms")
+    } else if milliseconds % 1000 == 0 {
+        format!("≤ {}s", milliseconds / 1000)
+    } else {
+        format!("≤ {}s", f64::from(milliseconds) / 1000.)
+    }
+}
@@ -1,12 +1,22 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use tdlib::types::FormattedText;
 
-use super::{CommandResult, CommandTrait};
+use super::{
+    CommandCategory, CommandResult, CommandTrait, InlineCommandResult, InlineCommandTrait,
+    MenuScope,
+};
 use crate::apis::translate;
+use crate::utilities::bot_state::BotState;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::endpoints::Endpoint;
 use crate::utilities::google_translate::SourceTargetLanguages;
+use crate::utilities::inline_query_context::{InlineArticle, InlineQueryContext};
 use crate::utilities::message_entities::ToEntity;
-use crate::utilities::{google_translate, message_entities};
+use crate::utilities::retry::RequestError;
+use crate::utilities::{google_translate, i18n, message_entities};
 
 pub struct Translate;
 
@@ -20,33 +30,110 @@ impl CommandTrait for Translate {
         Some("translate text using Google Translate")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("[source language] [target language] <text>")
+    }
+
+    fn arguments(&self) -> &[(&'static str, &'static str)] {
+        &[
+            ("source language", "language code or name, detected if omitted"),
+            ("target language", "language code or name, your app language if omitted"),
+        ]
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["hola", "polish good morning", "de pl guten Tag"]
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let (SourceTargetLanguages(source_language, target_language), StringGreedyOrReply(text)) =
             ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        let translation = translate::single(
-            ctx.bot_state.http_client.clone(),
-            &text,
-            source_language,
-            &target_language,
-        )
-        .await?;
+        let translation =
+            single_cached(&ctx.bot_state, &text, source_language, &target_language).await?;
 
-        let source_language = google_translate::get_language_name(&translation.source_language)
-            .unwrap_or(&translation.source_language);
+        ctx.reply_formatted_text(format_translation(&translation, &target_language)).await?;
 
-        let target_language =
-            google_translate::get_language_name(&target_language).unwrap_or(&target_language);
+        Ok(())
+    }
+}
 
-        ctx.reply_formatted_text(message_entities::formatted_text(vec![
-            source_language.bold(),
-            " ➜ ".text(),
-            target_language.bold(),
-            "\n".text(),
-            translation.text.text(),
-        ]))
-        .await?;
+#[async_trait]
+impl InlineCommandTrait for Translate {
+    fn inline_prefixes(&self) -> &[&str] {
+        &["tr", "translate"]
+    }
 
-        Ok(())
+    async fn execute_inline(
+        &self,
+        ctx: &InlineQueryContext,
+        arguments: String,
+    ) -> InlineCommandResult {
+        let (SourceTargetLanguages(source_language, target_language), text) =
+            SourceTargetLanguages::parse(&arguments, &ctx.user.language_code);
+
+        let text = text.trim_ascii();
+        if text.is_empty() {
+            return Err(i18n::text(ctx.language(), "translate_no_text").into());
+        }
+
+        let translation =
+            single_cached(&ctx.bot_state, text, source_language, &target_language).await?;
+
+        let formatted_text = format_translation(&translation, &target_language);
+
+        Ok(InlineArticle {
+            title: formatted_text.text.lines().next().unwrap_or_default().into(),
+            description: translation.text.clone(),
+            text: formatted_text,
+        })
     }
 }
+
+async fn single_cached(
+    bot_state: &BotState,
+    text: &str,
+    source_language: Option<&str>,
+    target_language: &str,
+) -> Result<Arc<translate::Translation>, RequestError> {
+    bot_state
+        .response_cache
+        .get_or_fetch(
+            Endpoint::Translate,
+            format!("{}:{target_language}:{text}", source_language.unwrap_or("auto")),
+            translate::single(
+                bot_state.http_client.clone(),
+                text,
+                source_language,
+                target_language,
+            ),
+        )
+        .await
+}
+
+fn format_translation(
+    translation: &translate::Translation,
+    target_language: &str,
+) -> FormattedText {
+    let source_language = google_translate::get_language_name(&translation.source_language)
+        .unwrap_or(&translation.source_language);
+
+    let target_language =
+        google_translate::get_language_name(target_language).unwrap_or(target_language);
+
+    message_entities::formatted_text(vec![
+        source_language.bold(),
+        " ➜ ".text(),
+        target_language.bold(),
+        "\n".text(),
+        translation.text.text(),
+    ])
+}
@@ -3,7 +3,7 @@ use std::iter;
 use async_trait::async_trait;
 use rand::seq::IndexedRandom;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait};
 use crate::apis::translate;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
@@ -23,6 +23,14 @@ impl CommandTrait for Trollslate {
         Some("translate text through many random languages")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<text>")
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let (target_language, StringGreedyOrReply(text)) =
             <(Option<Language>, _)>::convert(ctx, &arguments).await?.0;
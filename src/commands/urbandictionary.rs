@@ -3,10 +3,12 @@ use tdlib::types::FormattedText;
 use time::macros;
 use url::Url;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
 use crate::apis::urbandictionary::{self, Card};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::endpoints::Endpoint;
+use crate::utilities::i18n;
 use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned, ToNestedEntity};
 
 pub struct UrbanDictionary;
@@ -21,17 +23,41 @@ impl CommandTrait for UrbanDictionary {
         Some("get a word definition from Urban Dictionary")
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Text
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<term>")
+    }
+
+    fn examples(&self) -> &[&'static str] {
+        &["yeet"]
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(word) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
         ctx.send_typing().await?;
 
-        if let Ok(Some(definition)) =
-            urbandictionary::define(ctx.bot_state.http_client.clone(), &word).await
-        {
-            ctx.reply_formatted_text(format_definition(definition)).await?;
+        let definition = ctx
+            .bot_state
+            .response_cache
+            .get_or_fetch(
+                Endpoint::UrbanDictionary,
+                word.as_str(),
+                urbandictionary::define(ctx.bot_state.http_client.clone(), &word),
+            )
+            .await;
+
+        if let Ok(Some(definition)) = definition.as_deref() {
+            ctx.reply_formatted_text(format_definition(definition.clone())).await?;
         } else {
-            Err("sorry, there are no definitions for this word.")?;
+            Err(i18n::text(ctx.language(), "no_definitions"))?;
         }
 
         Ok(())
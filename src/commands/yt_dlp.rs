@@ -6,12 +6,15 @@ use tdlib::types::{
 };
 use tempfile::TempDir;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandCategory, CommandResult, CommandTrait, MenuScope};
+use crate::utilities::chat_action::Activity;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::job_queue::ConcurrencyClass;
 use crate::utilities::message_entities::{Entity, ToEntity, ToEntityOwned, ToNestedEntity};
+use crate::utilities::running_commands::Cleanup;
 use crate::utilities::yt_dlp::Infojson;
-use crate::utilities::{message_entities, yt_dlp};
+use crate::utilities::{i18n, message_entities, yt_dlp};
 
 const BRUH_EXTRACTORS: [&str; 45] = [
     "alphaporno",
@@ -95,11 +98,26 @@ impl CommandTrait for YtDlp {
         Some(self.command_description)
     }
 
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Media
+    }
+
+    fn menu_scopes(&self) -> &[MenuScope] {
+        &MenuScope::ALL
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<url>")
+    }
+
+    fn concurrency_class(&self) -> Option<ConcurrencyClass> {
+        Some(ConcurrencyClass::HeavyMedia)
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(argument) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        ctx.send_typing().await?;
-
+        let chat_action = ctx.keep_chat_action(Activity::Typing);
         let temp_dir = TempDir::new().unwrap();
 
         let (infojson_path, infojson) =
@@ -111,25 +129,33 @@ impl CommandTrait for YtDlp {
 
         if let Some(live_status) = &infojson.live_status {
             match live_status.as_str() {
-                "is_live" => return Err("cannot download livestreams.".into()),
-                "is_upcoming" => return Err("this is an upcoming livestream.".into()),
-                "post_live" => return Err("this livestream is still processing.".into()),
+                "is_live" => return Err(i18n::text(ctx.language(), "livestream").into()),
+                "is_upcoming" => {
+                    return Err(i18n::text(ctx.language(), "upcoming_livestream").into());
+                }
+                "post_live" => {
+                    return Err(i18n::text(ctx.language(), "livestream_processing").into());
+                }
                 _ => (),
             }
         }
 
         if let Some(duration) = infojson.duration {
             if duration > 60. * 60. {
-                return Err("cannot download media longer than 1h.".into());
+                return Err(
+                    i18n::format(ctx.language(), "media_too_long", &[("duration", &"1h")]).into()
+                );
             }
         } else if infojson.filesize_approx.is_none() {
-            return Err("cannot download media, because duration and filesize are unknown.".into());
+            return Err(i18n::text(ctx.language(), "media_size_unknown").into());
         }
 
         if let Some(filesize) = infojson.filesize_approx
             && filesize > 1024 * 1024 * 1024
         {
-            return Err("cannot download media larger than 1 GiB.".into());
+            return Err(
+                i18n::format(ctx.language(), "media_too_large", &[("size", &"1 GiB")]).into()
+            );
         }
 
         let channel = infojson.artist.clone().or_else(|| infojson.channel.clone());
@@ -148,7 +174,7 @@ impl CommandTrait for YtDlp {
             .message_queue
             .wait_for_message(
                 ctx.reply_formatted_text(message_entities::formatted_text(vec![
-                    "downloading ".text(),
+                    i18n::text(ctx.language(), "downloading").text(),
                     media_name.clone().unwrap_or_else(|| infojson.webpage_url.text()),
                     "…".text(),
                 ]))
@@ -157,19 +183,18 @@ impl CommandTrait for YtDlp {
             )
             .await?;
 
+        ctx.add_cleanup(Cleanup::DeleteMessage(status_message.id));
+
         yt_dlp::download_from_infojson(temp_dir.path(), &infojson_path, self.format).await?;
 
+        let content = get_message_content(&temp_dir, infojson, media_name, channel, title);
+        chat_action.set(Activity::of_content(&content));
+
         ctx.bot_state
             .message_queue
-            .wait_for_message(
-                ctx.reply_custom(
-                    get_message_content(&temp_dir, infojson, media_name, channel, title),
-                    None,
-                )
-                .await?
-                .id,
-            )
+            .wait_for_message(ctx.reply_custom(content, None).await?.id)
             .await?;
+        drop(chat_action);
 
         ctx.delete_message(status_message.id).await?;
         temp_dir.close().unwrap();
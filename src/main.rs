@@ -1,5 +1,8 @@
+use std::process;
+
 use bot::Bot;
 use utilities::logchamp;
+use utilities::settings::{self, ModelProvider, Settings};
 
 mod apis;
 mod bot;
@@ -8,12 +11,29 @@ mod utilities;
 
 #[tokio::main]
 async fn main() {
-    logchamp::init();
     dotenvy::dotenv().ok();
+    logchamp::init();
 
-    let mut bot = Bot::new();
+    match Settings::load() {
+        Ok(loaded) => settings::set(loaded),
+        Err(err) => {
+            log::error!("{err}");
+            log::logger().flush();
+            process::exit(1);
+        }
+    }
+
+    let mut bot = match Bot::new() {
+        Ok(bot) => bot,
+        Err(err) => {
+            log::error!("failed to load the bot state: {err}");
+            log::logger().flush();
+            process::exit(1);
+        }
+    };
 
     bot.add_command(commands::start::Start);
+    bot.add_command(commands::help::Help);
     bot.add_command(commands::fal::Fal::sana());
     bot.add_command(commands::fal::Fal::sdxl());
     bot.add_command(commands::stablehorde::StableHorde::stable_diffusion());
@@ -22,15 +42,22 @@ async fn main() {
     bot.add_command(commands::stablehorde::StableHorde::furry_diffusion());
     bot.add_command(commands::markov_chain::MarkovChain);
     bot.add_command(commands::config::Config);
+    bot.add_command(commands::alias::Alias);
+    bot.add_command(commands::alias::Unalias);
+    bot.add_command(commands::notes::Save);
+    bot.add_command(commands::notes::Get);
+    bot.add_command(commands::notes::Notes);
+    bot.add_command(commands::notes::Clear);
     bot.add_command(commands::different_dimension_me::DifferentDimensionMe);
-    bot.add_command(commands::gemini::Gemini::gemini());
-    bot.add_command(commands::gemini::Gemini::gemini_lite());
-    bot.add_command(commands::groq::Groq::gpt_oss());
-    bot.add_command(commands::groq::Groq::llama4());
-    bot.add_command(commands::groq::Groq::llama3());
-    bot.add_command(commands::groq::Groq::kimi_k2());
-    bot.add_command(commands::openrouter::OpenRouter::mistral());
-    bot.add_command(commands::openrouter::OpenRouter::perplexity());
+    for model in &settings::get().models {
+        match model.provider {
+            ModelProvider::Gemini => bot.add_command(commands::gemini::Gemini::new(model)),
+            ModelProvider::Groq => bot.add_command(commands::groq::Groq::new(model)),
+            ModelProvider::OpenRouter => {
+                bot.add_command(commands::openrouter::OpenRouter::new(model));
+            }
+        }
+    }
     bot.add_command(commands::translate::Translate);
     bot.add_command(commands::badtranslate::BadTranslate);
     bot.add_command(commands::trollslate::Trollslate);
@@ -53,9 +80,26 @@ async fn main() {
     bot.add_command(commands::moveit_joke::MoveitJoke);
     bot.add_command(commands::kebab::Kebab);
     bot.add_command(commands::ping::Ping);
+    bot.add_command(commands::cancel::Cancel);
     bot.add_command(commands::delete::Delete);
+    bot.add_command(commands::reload::Reload);
+    bot.add_command(commands::broadcast::Broadcast);
+    bot.add_command(commands::leave::Leave);
+    bot.add_command(commands::block::Block);
+    bot.add_command(commands::block::Unblock);
+    bot.add_command(commands::stats::Stats);
     bot.add_command(commands::sex::Sex);
 
+    bot.add_inline_command(commands::translate::Translate);
+    bot.add_inline_command(commands::convert::Convert);
+    bot.add_inline_command(commands::charinfo::CharInfo);
+
+    if let Some(name) = bot.duplicate_command_name() {
+        log::error!("command name {name} is used by more than one command");
+        log::logger().flush();
+        process::exit(1);
+    }
+
     bot.run();
     log::logger().flush();
 }
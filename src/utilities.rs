@@ -1,22 +1,40 @@
 pub mod api_utils;
 pub mod bot_state;
 pub mod cache;
+pub mod callback_context;
+pub mod chat_action;
 pub mod command_context;
 pub mod command_dispatcher;
 pub mod command_manager;
+pub mod command_replies;
 pub mod config;
 pub mod convert_argument;
+pub mod endpoints;
 pub mod ffprobe;
 pub mod file_download;
 pub mod google_translate;
+pub mod i18n;
 pub mod image_utils;
+pub mod inline_command_manager;
+pub mod inline_query_context;
+pub mod job_queue;
 pub mod logchamp;
 pub mod markov_chain_manager;
 pub mod message_entities;
 pub mod message_filters;
 pub mod message_queue;
+pub mod metrics;
+pub mod notes;
 pub mod parsed_command;
+pub mod persistence;
 pub mod rate_limit;
+pub mod response_cache;
+pub mod retry;
+pub mod running_commands;
+pub mod settings;
+pub mod stats;
+pub mod storage;
+pub mod telegram_client;
 pub mod telegram_utils;
 pub mod text_utils;
 pub mod yt_dlp;
@@ -1,21 +1,26 @@
-use std::env;
 use std::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use markov_chain::MarkovChain;
 use reqwest::{Client, redirect};
 use tdlib::enums::{ChatMember, ChatMemberStatus, MessageSender};
 use tdlib::functions;
 use tdlib::types::MessageSenderUser;
-use tokio::sync::Mutex as TokioMutex;
 
 use super::cache::Cache;
+use super::command_manager::CommandManager;
+use super::command_replies::CommandReplies;
 use super::config::Config;
-use super::markov_chain_manager;
+use super::job_queue::JobQueue;
 use super::message_queue::MessageQueue;
-use super::rate_limit::{RateLimiter, RateLimits};
-use crate::apis::coinranking::Coin;
-use crate::apis::eurofxref::Rate;
+use super::metrics::Metrics;
+use super::notes::Notes;
+use super::rate_limit::RateLimiter;
+use super::response_cache::ResponseCache;
+use super::running_commands::RunningCommands;
+use super::stats::Stats;
+use super::storage::{SqliteStorage, Storage, StorageError};
+use super::{persistence, settings};
 use crate::bot::TdResult;
 
 #[derive(Clone, Copy)]
@@ -26,44 +31,88 @@ pub enum BotStatus {
     Closed,
 }
 
-pub struct Currencies {
-    pub updated_at: Instant,
-    pub fiat: Vec<Rate>,
-    pub crypto: Vec<Coin>,
-}
-
 pub struct BotState {
     pub status: Mutex<BotStatus>,
     pub config: Mutex<Config>,
     pub cache: Mutex<Cache>,
+    pub command_manager: Mutex<CommandManager>,
     pub http_client: Client,
-    pub currencies: TokioMutex<Option<Currencies>>,
+    pub response_cache: ResponseCache,
     pub message_queue: MessageQueue,
-    pub rate_limits: Mutex<RateLimits>,
+    pub metrics: Metrics,
+    pub command_replies: Mutex<CommandReplies>,
+    pub job_queue: JobQueue,
+    pub rate_limiter: Mutex<RateLimiter>,
+    pub running_commands: Mutex<RunningCommands>,
+    pub stats: Mutex<Stats>,
     pub markov_chain: Mutex<MarkovChain>,
+    pub notes: Mutex<Notes>,
+    pub storage: Box<dyn Storage>,
 }
 
 impl BotState {
-    pub fn new() -> Self {
+    pub fn new() -> Result<Self, StorageError> {
+        Self::with_storage(Box::new(SqliteStorage::open()?))
+    }
+
+    /// loads the saved state from the given storage
+    pub fn with_storage(storage: Box<dyn Storage>) -> Result<Self, StorageError> {
         let mut http_client = Client::builder();
 
-        if let Ok(user_agent) = env::var("USER_AGENT") {
+        if let Some(user_agent) = &settings::get().user_agent {
             http_client = http_client.user_agent(user_agent);
         }
 
-        Self {
+        Ok(Self {
             status: Mutex::new(BotStatus::Closed),
-            config: Mutex::new(Config::load().unwrap()),
+            config: Mutex::new(persistence::load(&*storage)?),
             cache: Mutex::new(Cache::default()),
+            command_manager: Mutex::new(CommandManager::new()),
             http_client: http_client
                 .redirect(redirect::Policy::none())
                 .timeout(Duration::from_mins(5))
                 .build()
                 .unwrap(),
-            currencies: TokioMutex::new(None),
-            rate_limits: Mutex::new(RateLimits { rate_limit_exceeded: RateLimiter::new(1, 20) }),
+            response_cache: ResponseCache::default(),
+            rate_limiter: Mutex::new(if settings::get().persist_rate_limits {
+                persistence::load(&*storage)?
+            } else {
+                RateLimiter::default()
+            }),
             message_queue: MessageQueue::default(),
-            markov_chain: Mutex::new(markov_chain_manager::load().unwrap()),
+            metrics: Metrics::default(),
+            command_replies: Mutex::new(CommandReplies::default()),
+            job_queue: JobQueue::default(),
+            running_commands: Mutex::new(RunningCommands::default()),
+            stats: Mutex::new(persistence::load(&*storage)?),
+            markov_chain: Mutex::new(persistence::load(&*storage)?),
+            notes: Mutex::new(persistence::load(&*storage)?),
+            storage,
+        })
+    }
+
+    /// saves the state which should survive a restart
+    pub fn save(&self) {
+        if let Err(err) = persistence::save(&*self.storage, &self.config) {
+            log::error!("failed to save bot config: {err}");
+        }
+
+        if let Err(err) = persistence::save(&*self.storage, &self.stats) {
+            log::error!("failed to save command stats: {err}");
+        }
+
+        if let Err(err) = persistence::save(&*self.storage, &self.markov_chain) {
+            log::error!("failed to save Markov chain: {err}");
+        }
+
+        if let Err(err) = persistence::save(&*self.storage, &self.notes) {
+            log::error!("failed to save notes: {err}");
+        }
+
+        if settings::get().persist_rate_limits
+            && let Err(err) = persistence::save(&*self.storage, &self.rate_limiter)
+        {
+            log::error!("failed to save rate limits: {err}");
         }
     }
 
@@ -94,4 +143,15 @@ impl BotState {
 
         Ok(chat_member.status)
     }
+
+    pub async fn is_chat_admin(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        client_id: i32,
+    ) -> TdResult<bool> {
+        let status = self.get_member_status(chat_id, user_id, client_id).await?;
+
+        Ok(matches!(status, ChatMemberStatus::Creator(_) | ChatMemberStatus::Administrator(_)))
+    }
 }
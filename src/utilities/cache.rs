@@ -25,6 +25,19 @@ impl Cache {
         self.users.get(&id).cloned()
     }
 
+    /// IDs of groups the bot knows about, excluding channels
+    pub fn group_chat_ids(&self) -> Vec<i64> {
+        self.chats
+            .iter()
+            .filter(|(_, chat)| match &chat.r#type {
+                ChatType::BasicGroup(_) => true,
+                ChatType::Supergroup(supergroup) => !supergroup.is_channel,
+                _ => false,
+            })
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
     pub fn get_member_status(&self, chat_id: i64, member_id: i64) -> Option<ChatMemberStatus> {
         self.member_status.get(&(chat_id, member_id)).cloned()
     }
@@ -49,6 +62,11 @@ impl Cache {
         }
     }
 
+    #[cfg(test)]
+    pub fn insert_user(&mut self, user: CompactUser) {
+        self.users.insert(user.id, user);
+    }
+
     pub fn update_user(&mut self, update: UpdateUser) {
         self.users.insert(update.user.id, update.user.into());
     }
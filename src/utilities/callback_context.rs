@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use tdlib::enums::{InputMessageContent, ReplyMarkup};
+use tdlib::types::{FormattedText, InputMessageText, LinkPreviewOptions, Message};
+
+use super::bot_state::BotState;
+use super::cache::{CompactChat, CompactUser};
+use super::i18n::{self, Language};
+use super::telegram_client::TelegramClient;
+use crate::bot::TdResult;
+
+pub struct CallbackContext {
+    pub client_id: i32,
+    pub client: Arc<dyn TelegramClient>,
+    pub query_id: i64,
+    pub chat_id: i64,
+    pub message_id: i64,
+    pub chat: CompactChat,
+    pub user: CompactUser,
+    pub bot_state: Arc<BotState>,
+}
+
+impl CallbackContext {
+    pub fn language(&self) -> Language {
+        i18n::language(&self.bot_state, Some(self.chat_id), &self.user)
+    }
+
+    pub async fn answer(&self, text: String, show_alert: bool) -> TdResult<()> {
+        self.client.answer_callback_query(self.query_id, text, show_alert).await
+    }
+
+    pub async fn edit_message_formatted_text(
+        &self,
+        text: FormattedText,
+        reply_markup: Option<ReplyMarkup>,
+    ) -> TdResult<Message> {
+        self.client
+            .edit_message_text(
+                self.chat_id,
+                self.message_id,
+                reply_markup,
+                InputMessageContent::InputMessageText(InputMessageText {
+                    text,
+                    link_preview_options: Some(LinkPreviewOptions {
+                        is_disabled: true,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            )
+            .await
+    }
+
+    pub async fn edit_message(
+        &self,
+        text: String,
+        reply_markup: Option<ReplyMarkup>,
+    ) -> TdResult<Message> {
+        self.edit_message_formatted_text(FormattedText { text, ..Default::default() }, reply_markup)
+            .await
+    }
+
+    pub async fn delete_message(&self) -> TdResult<()> {
+        self.client.delete_messages(self.chat_id, vec![self.message_id]).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_answer_and_edit() {
+        let (command_ctx, client) = test_fixtures::fake_command_context();
+        let ctx = CallbackContext {
+            client_id: 0,
+            client: client.clone(),
+            query_id: 0,
+            chat_id: command_ctx.message.chat_id,
+            message_id: command_ctx.message.id,
+            chat: command_ctx.chat,
+            user: command_ctx.user,
+            bot_state: command_ctx.bot_state,
+        };
+
+        ctx.answer("answer".into(), false).await.unwrap();
+        ctx.edit_message("edited".into(), None).await.unwrap();
+
+        assert_eq!(*client.callback_answers.lock().unwrap(), ["answer"]);
+        assert_eq!(client.edited.lock().unwrap()[0].text(), Some("edited"));
+    }
+}
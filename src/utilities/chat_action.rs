@@ -0,0 +1,129 @@
+//! chat actions shown to users below the chat title while the bot works on a
+//! reply
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tdlib::enums::{self, ChatAction, InputMessageContent};
+use tdlib::types::{
+    ChatActionUploadingDocument, ChatActionUploadingPhoto, ChatActionUploadingVideo,
+    ChatActionUploadingVideoNote, ChatActionUploadingVoiceNote,
+};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::telegram_client::TelegramClient;
+
+/// clients hide a chat action after 5 seconds unless it's sent again
+const REFRESH_INTERVAL: Duration = Duration::from_secs(4);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Activity {
+    Typing,
+    UploadingPhoto,
+    UploadingVideo,
+    UploadingVideoNote,
+    UploadingVoiceNote,
+    UploadingDocument,
+}
+
+impl Activity {
+    /// the activity of sending a message with the given content
+    pub const fn of_content(content: &InputMessageContent) -> Self {
+        match content {
+            InputMessageContent::InputMessagePhoto(_) => Self::UploadingPhoto,
+            InputMessageContent::InputMessageVideo(_)
+            | InputMessageContent::InputMessageAnimation(_) => Self::UploadingVideo,
+            InputMessageContent::InputMessageVideoNote(_) => Self::UploadingVideoNote,
+            InputMessageContent::InputMessageVoiceNote(_) => Self::UploadingVoiceNote,
+            InputMessageContent::InputMessageAudio(_)
+            | InputMessageContent::InputMessageDocument(_) => Self::UploadingDocument,
+            _ => Self::Typing,
+        }
+    }
+
+    pub const fn chat_action(self) -> ChatAction {
+        match self {
+            Self::Typing => ChatAction::Typing,
+            Self::UploadingPhoto => {
+                ChatAction::UploadingPhoto(ChatActionUploadingPhoto { progress: 0 })
+            }
+            Self::UploadingVideo => {
+                ChatAction::UploadingVideo(ChatActionUploadingVideo { progress: 0 })
+            }
+            Self::UploadingVideoNote => {
+                ChatAction::UploadingVideoNote(ChatActionUploadingVideoNote { progress: 0 })
+            }
+            Self::UploadingVoiceNote => {
+                ChatAction::UploadingVoiceNote(ChatActionUploadingVoiceNote { progress: 0 })
+            }
+            Self::UploadingDocument => {
+                ChatAction::UploadingDocument(ChatActionUploadingDocument { progress: 0 })
+            }
+        }
+    }
+}
+
+/// keeps showing a chat action until dropped. the activity can be changed as
+/// the job progresses, e.g. once a download finishes and the upload starts.
+pub struct ChatActionKeeper {
+    activity: watch::Sender<Activity>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl ChatActionKeeper {
+    pub fn start(
+        client: Arc<dyn TelegramClient>,
+        chat_id: i64,
+        topic_id: Option<enums::MessageTopic>,
+        activity: Activity,
+    ) -> Self {
+        let (sender, mut receiver) = watch::channel(activity);
+
+        // like in `CommandContext::send_chat_action`, only topics get chat actions
+        let Some(topic_id) = topic_id else {
+            return Self { activity: sender, task: None };
+        };
+
+        let task = tokio::spawn(async move {
+            loop {
+                let activity = *receiver.borrow_and_update();
+
+                if let Err(err) =
+                    client.send_chat_action(chat_id, topic_id.clone(), activity.chat_action()).await
+                {
+                    log::warn!("failed to send chat action: {}", err.message);
+                    return;
+                }
+
+                tokio::select! {
+                    () = tokio::time::sleep(REFRESH_INTERVAL) => {}
+                    result = receiver.changed() => {
+                        if result.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self { activity: sender, task: Some(task) }
+    }
+
+    /// shows the new activity right away
+    pub fn set(&self, activity: Activity) {
+        self.activity.send_if_modified(|current| {
+            let changed = *current != activity;
+            *current = activity;
+            changed
+        });
+    }
+}
+
+impl Drop for ChatActionKeeper {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
@@ -1,17 +1,21 @@
 use std::sync::Arc;
 
-use tdlib::enums::{self, ChatAction, InputMessageContent, InputMessageReplyTo};
-use tdlib::functions;
+use tdlib::enums::{self, InputMessageContent, InputMessageReplyTo};
 use tdlib::types::{
     FormattedText, InputMessageReplyToMessage, InputMessageText, LinkPreviewOptions, Message,
 };
 
 use super::bot_state::BotState;
 use super::cache::{CompactChat, CompactUser};
+use super::chat_action::{Activity, ChatActionKeeper};
+use super::i18n::{self, Language};
+use super::running_commands::Cleanup;
+use super::telegram_client::TelegramClient;
 use crate::bot::TdResult;
 
 pub struct CommandContext {
     pub client_id: i32,
+    pub client: Arc<dyn TelegramClient>,
     pub chat: CompactChat,
     pub user: CompactUser,
     pub message: Message,
@@ -19,24 +23,65 @@ pub struct CommandContext {
 }
 
 impl CommandContext {
+    pub fn language(&self) -> Language {
+        i18n::language(&self.bot_state, Some(self.message.chat_id), &self.user)
+    }
+
     pub async fn reply_custom(
         &self,
         message_content: InputMessageContent,
         reply_markup: Option<enums::ReplyMarkup>,
     ) -> TdResult<Message> {
-        let enums::Message::Message(message) = functions::send_message(
-            self.message.chat_id,
-            self.message.topic_id.clone(),
-            Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
-                message_id: self.message.id,
-                ..Default::default()
-            })),
-            None,
-            reply_markup,
-            message_content,
-            self.client_id,
-        )
-        .await?;
+        let reply_to_edit = self
+            .bot_state
+            .command_replies
+            .lock()
+            .unwrap()
+            .take_reply_to_edit(self.message.chat_id, self.message.id);
+
+        if let Some(reply_id) = reply_to_edit {
+            if let InputMessageContent::InputMessageText(_) = message_content {
+                match self
+                    .client
+                    .edit_message_text(
+                        self.message.chat_id,
+                        reply_id,
+                        reply_markup.clone(),
+                        message_content.clone(),
+                    )
+                    .await
+                {
+                    Ok(message) => {
+                        self.bot_state.message_queue.message_edited(message.clone());
+                        return Ok(message);
+                    }
+                    Err(err) => log::warn!("failed to edit the previous reply: {}", err.message),
+                }
+            } else {
+                self.delete_message(reply_id).await.ok();
+            }
+        }
+
+        let message = self
+            .client
+            .send_message(
+                self.message.chat_id,
+                self.message.topic_id.clone(),
+                Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
+                    message_id: self.message.id,
+                    ..Default::default()
+                })),
+                reply_markup,
+                message_content,
+            )
+            .await?;
+
+        let mut command_replies = self.bot_state.command_replies.lock().unwrap();
+        if reply_to_edit.is_some() {
+            command_replies.replace_reply(self.message.chat_id, self.message.id, message.id);
+        } else {
+            command_replies.set_reply(self.message.chat_id, self.message.id, message.id);
+        }
 
         Ok(message)
     }
@@ -77,23 +122,21 @@ impl CommandContext {
         message_id: i64,
         text: FormattedText,
     ) -> TdResult<Message> {
-        let enums::Message::Message(message) = functions::edit_message_text(
-            self.message.chat_id,
-            message_id,
-            None,
-            InputMessageContent::InputMessageText(InputMessageText {
-                text,
-                link_preview_options: Some(LinkPreviewOptions {
-                    is_disabled: true,
+        self.client
+            .edit_message_text(
+                self.message.chat_id,
+                message_id,
+                None,
+                InputMessageContent::InputMessageText(InputMessageText {
+                    text,
+                    link_preview_options: Some(LinkPreviewOptions {
+                        is_disabled: true,
+                        ..Default::default()
+                    }),
                     ..Default::default()
                 }),
-                ..Default::default()
-            }),
-            self.client_id,
-        )
-        .await?;
-
-        Ok(message)
+            )
+            .await
     }
 
     pub async fn edit_message(&self, message_id: i64, text: String) -> TdResult<Message> {
@@ -102,27 +145,113 @@ impl CommandContext {
     }
 
     pub async fn delete_messages(&self, message_ids: Vec<i64>) -> TdResult<()> {
-        functions::delete_messages(self.message.chat_id, message_ids, true, self.client_id).await
+        self.client.delete_messages(self.message.chat_id, message_ids).await
     }
 
     pub async fn delete_message(&self, message_id: i64) -> TdResult<()> {
         self.delete_messages(vec![message_id]).await
     }
 
-    pub async fn send_typing(&self) -> TdResult<()> {
-        // HACK(jel): TDLib's documentation doesn't mention that `topic_id` is optional,
-        // so such request cannot currently be constructed
-        let Some(topic_id) = &self.message.topic_id else {
+    pub fn add_cleanup(&self, cleanup: Cleanup) {
+        self.bot_state.running_commands.lock().unwrap().add_cleanup(
+            self.message.chat_id,
+            self.message.id,
+            cleanup,
+        );
+    }
+
+    pub async fn send_chat_action(&self, activity: Activity) -> TdResult<()> {
+        // HACK(jel): TDLib's documentation doesn't mention that `topic_id` is
+        // optional, so such request cannot currently be constructed
+        let Some(topic_id) = self.message.topic_id.clone() else {
             return Ok(());
         };
 
-        functions::send_chat_action(
+        self.client.send_chat_action(self.message.chat_id, topic_id, activity.chat_action()).await
+    }
+
+    pub async fn send_typing(&self) -> TdResult<()> {
+        self.send_chat_action(Activity::Typing).await
+    }
+
+    /// keeps showing the activity until the returned value is dropped, for jobs
+    /// which take longer than a single chat action lasts
+    pub fn keep_chat_action(&self, activity: Activity) -> ChatActionKeeper {
+        ChatActionKeeper::start(
+            self.client.clone(),
             self.message.chat_id,
-            topic_id.clone(),
-            String::new(),
-            Some(ChatAction::Typing),
-            self.client_id,
+            self.message.topic_id.clone(),
+            activity,
         )
-        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tdlib::enums::{ChatAction, MessageTopic};
+
+    use crate::utilities::chat_action::Activity;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_rerun_edits_reply() {
+        let (ctx, client) = test_fixtures::fake_command_context();
+        ctx.bot_state.command_replies.lock().unwrap().insert(&ctx.message);
+
+        let message = ctx.reply("first".into()).await.unwrap();
+        let message = ctx.bot_state.message_queue.wait_for_message(message.id).await.unwrap();
+        assert_eq!(client.sent_texts(), ["first"]);
+
+        ctx.bot_state
+            .command_replies
+            .lock()
+            .unwrap()
+            .rerun(ctx.message.chat_id, ctx.message.id)
+            .unwrap();
+
+        ctx.reply("second".into()).await.unwrap();
+        assert_eq!(client.sent_texts(), ["first"]);
+
+        let edited = client.edited.lock().unwrap();
+        assert_eq!(edited.len(), 1);
+        assert_eq!((edited[0].chat_id, edited[0].message_id), (message.chat_id, message.id));
+        assert_eq!(edited[0].text(), Some("second"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keep_chat_action() {
+        let (mut ctx, client) = test_fixtures::fake_command_context();
+        ctx.message.topic_id = Some(MessageTopic::default());
+
+        let chat_action = ctx.keep_chat_action(Activity::Typing);
+        tokio::task::yield_now().await;
+        chat_action.set(Activity::UploadingVideo);
+        tokio::task::yield_now().await;
+        tokio::time::advance(Duration::from_secs(4)).await;
+        tokio::task::yield_now().await;
+        drop(chat_action);
+        tokio::time::advance(Duration::from_secs(4)).await;
+        tokio::task::yield_now().await;
+
+        assert!(matches!(
+            client.chat_actions.lock().unwrap()[..],
+            [ChatAction::Typing, ChatAction::UploadingVideo(_), ChatAction::UploadingVideo(_)]
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_chat_action_outside_of_topic() {
+        let (ctx, client) = test_fixtures::fake_command_context();
+        assert!(ctx.message.topic_id.is_none());
+
+        ctx.send_typing().await.unwrap();
+        let chat_action = ctx.keep_chat_action(Activity::UploadingVideo);
+        tokio::time::advance(Duration::from_secs(4)).await;
+        tokio::task::yield_now().await;
+        drop(chat_action);
+
+        assert!(client.chat_actions.lock().unwrap().is_empty());
     }
 }
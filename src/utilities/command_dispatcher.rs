@@ -1,10 +1,25 @@
 use std::error::Error;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 
+use futures_util::future;
+use tdlib::types::FormattedText;
+use time::OffsetDateTime;
+
+use super::bot_state::BotState;
+use super::cache::CompactUser;
+use super::callback_context::CallbackContext;
 use super::command_context::CommandContext;
 use super::command_manager::CommandInstance;
 use super::file_download::DownloadError;
+use super::i18n::{self, Language};
+use super::inline_command_manager::InlineCommandRef;
+use super::inline_query_context::{InlineArticle, InlineQueryContext};
+use super::job_queue::{ConcurrencyClass, Job};
+use super::rate_limit::{RateLimit, RateLimitScope};
+use super::running_commands::Cleanup;
+use super::settings;
 use crate::bot::TdResult;
 use crate::commands::CommandError;
 use crate::utilities::text_utils;
@@ -13,9 +28,64 @@ pub async fn dispatch_command(
     command: Arc<CommandInstance>,
     arguments: String,
     context: CommandContext,
+    admin_only: bool,
 ) {
-    if let Some(cooldown) = check_rate_limit(&command, &context) {
-        if let Err(err) = Box::pin(report_rate_limit(&context, cooldown)).await {
+    if !has_required_role(&command, &context.user) {
+        log::info!(
+            "{command} is restricted to {:?} for {}",
+            command.command.required_role(),
+            context.user
+        );
+        return;
+    }
+
+    if admin_only {
+        match context
+            .bot_state
+            .is_chat_admin(context.message.chat_id, context.user.id, context.client_id)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                log::info!("{command} is restricted to admins in {}", context.chat);
+
+                if let Err(err) =
+                    context.reply(i18n::text(context.language(), "command_admin_only").into()).await
+                {
+                    log::error!(
+                        "TDLib error occurred while reporting an admin-only command: {}: {}",
+                        err.code,
+                        err.message
+                    );
+                }
+
+                return;
+            }
+            Err(err) => {
+                log::error!(
+                    "TDLib error occurred while checking admin status: {}: {}",
+                    err.code,
+                    err.message
+                );
+                return;
+            }
+        }
+    }
+
+    // edited commands are re-runs, which were already charged when the message was
+    // sent, and are limited per message instead
+    let rerun = context.message.edit_date != 0;
+
+    if !rerun
+        && let Some((scope, cooldown)) = check_rate_limit(
+            &context.bot_state,
+            &command,
+            &context.user,
+            context.message.chat_id,
+            context.message.date.into(),
+        )
+    {
+        if let Err(err) = Box::pin(report_rate_limit(&context, scope, cooldown)).await {
             log::error!(
                 "TDLib error occurred while reporting a rate limit: {}: {}",
                 err.code,
@@ -25,9 +95,75 @@ pub async fn dispatch_command(
         return;
     }
 
-    log::info!("running {command} {:?} for {} in {}", arguments, context.user, context.chat);
+    log::info!(
+        command = command.name(),
+        chat_id = context.message.chat_id,
+        user_id = context.user.id;
+        "running {command} {:?} for {} in {}", arguments, context.user, context.chat
+    );
+
+    context.bot_state.command_replies.lock().unwrap().insert(&context.message);
+
+    // time spent queued for a job doesn't count towards the latency
+    let started_at = OnceLock::new();
+
+    let (execute, abort_handle) = future::abortable(async {
+        let _job = match command.command.concurrency_class() {
+            Some(class) => Some(Box::pin(wait_for_job(&context, class)).await?),
+            None => None,
+        };
+
+        started_at.set(Instant::now()).ok();
+
+        command.command.execute(&context, arguments).await
+    });
+
+    let (run_id, previous_cleanups) = context.bot_state.running_commands.lock().unwrap().insert(
+        context.message.chat_id,
+        context.message.id,
+        context.user.id,
+        abort_handle,
+    );
+
+    for cleanup in previous_cleanups {
+        cleanup
+            .run(context.message.chat_id, context.bot_state.http_client.clone(), context.client_id)
+            .await;
+    }
+
+    let result = execute.await;
+
+    context.bot_state.running_commands.lock().unwrap().remove(
+        context.message.chat_id,
+        context.message.id,
+        run_id,
+    );
+
+    let error = match &result {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err.kind()),
+        Err(_) => Some("Cancelled"),
+    };
+
+    context.bot_state.stats.lock().unwrap().record(
+        command.name(),
+        context.user.id,
+        started_at.get().map_or(Duration::ZERO, Instant::elapsed),
+        error,
+        OffsetDateTime::now_utc().unix_timestamp(),
+    );
+
+    let Ok(result) = result else {
+        log::info!(
+            command = command.name(),
+            chat_id = context.message.chat_id,
+            user_id = context.user.id;
+            "{command} cancelled by {} in {}", context.user, context.chat
+        );
+        return;
+    };
 
-    if let Err(err) = command.command.execute(&context, arguments).await
+    if let Err(err) = result
         && let Err(err) = report_command_error(command, &context, err).await
     {
         log::error!(
@@ -38,32 +174,208 @@ pub async fn dispatch_command(
     }
 }
 
-fn check_rate_limit(command: &CommandInstance, context: &CommandContext) -> Option<u64> {
-    let cooldown = command
-        .rate_limiter
-        .lock()
-        .unwrap()
-        .update_rate_limit(context.user.id, context.message.date)?
-        .try_into()
-        .unwrap();
+async fn wait_for_job(
+    context: &CommandContext,
+    class: ConcurrencyClass,
+) -> Result<Job<'_>, CommandError> {
+    let mut job = context.bot_state.job_queue.enqueue(class);
+
+    if job.position() == 0 {
+        return Ok(job);
+    }
+
+    log::info!("queued {class:?} job for {} at position {}", context.user, job.position());
+
+    let status_msg = context
+        .bot_state
+        .message_queue
+        .wait_for_message(
+            context.reply(format_queue_position(context.language(), job.position())).await?.id,
+        )
+        .await?;
+
+    context.add_cleanup(Cleanup::DeleteMessage(status_msg.id));
+
+    loop {
+        let position = job.wait().await;
+
+        if position == 0 {
+            break;
+        }
+
+        context
+            .edit_message(status_msg.id, format_queue_position(context.language(), position))
+            .await?;
+    }
+
+    context.delete_message(status_msg.id).await.ok();
+
+    Ok(job)
+}
+
+fn format_queue_position(language: Language, position: usize) -> String {
+    i18n::format(language, "queue_position", &[("position", &position)])
+}
+
+pub async fn dispatch_callback(
+    command: Arc<CommandInstance>,
+    payload: String,
+    context: CallbackContext,
+    admin_only: bool,
+) {
+    if !has_required_role(&command, &context.user) {
+        context.answer(i18n::text(context.language(), "button_forbidden").into(), false).await.ok();
+        return;
+    }
+
+    if admin_only {
+        match context
+            .bot_state
+            .is_chat_admin(context.chat_id, context.user.id, context.client_id)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                log::info!("{command} callback is restricted to admins in {}", context.chat);
+                context
+                    .answer(i18n::text(context.language(), "button_admin_only").into(), false)
+                    .await
+                    .ok();
+                return;
+            }
+            Err(err) => {
+                log::error!(
+                    "TDLib error occurred while checking admin status: {}: {}",
+                    err.code,
+                    err.message
+                );
+                return;
+            }
+        }
+    }
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+
+    if let Some((scope, cooldown)) =
+        check_rate_limit(&context.bot_state, &command, &context.user, context.chat_id, now)
+    {
+        if let Err(err) =
+            context.answer(format_rate_limit(context.language(), scope, cooldown), false).await
+        {
+            log::error!(
+                "TDLib error occurred while reporting a rate limit: {}: {}",
+                err.code,
+                err.message
+            );
+        }
+
+        return;
+    }
+
+    log::info!("running {command} callback {:?} for {} in {}", payload, context.user, context.chat);
+
+    let result = match command.command.callback(&context, payload).await {
+        Ok(()) => context.answer(String::new(), false).await,
+        Err(err) => {
+            context.answer(format_command_error(&command, context.language(), err).text, true).await
+        }
+    };
+
+    if let Err(err) = result {
+        log::error!(
+            "TDLib error occurred while answering a callback query: {}: {}",
+            err.code,
+            err.message
+        );
+    }
+}
+
+pub async fn dispatch_inline_command(
+    command: InlineCommandRef,
+    arguments: String,
+    context: InlineQueryContext,
+) {
+    let name = format!("inline {}", command.inline_prefixes().first().unwrap());
+
+    log::info!("running {name} {:?} for {}", arguments, context.user);
+
+    let result = match command.execute_inline(&context, arguments).await {
+        Ok(article) => context.answer_article(article, 300).await,
+        Err(err) => {
+            let text = format_command_error(&name, context.language(), err);
+            let article =
+                InlineArticle { title: text.text.clone(), description: String::new(), text };
+            context.answer_article(article, 0).await
+        }
+    };
+
+    if let Err(err) = result {
+        log::error!(
+            "TDLib error occurred while answering an inline query: {}: {}",
+            err.code,
+            err.message
+        );
+    }
+}
+
+fn has_required_role(command: &CommandInstance, user: &CompactUser) -> bool {
+    settings::get().role(user.id) >= command.command.required_role()
+}
+
+fn check_rate_limit(
+    bot_state: &BotState,
+    command: &CommandInstance,
+    user: &CompactUser,
+    chat_id: i64,
+    time: i64,
+) -> Option<(RateLimitScope, u64)> {
+    let (scope, cooldown) = bot_state.rate_limiter.lock().unwrap().check(
+        command.name(),
+        user.id,
+        chat_id,
+        &command.rate_limits,
+        time,
+    )?;
 
     log::info!(
-        "{command} rate limit exceeded by {} by {}",
-        text_utils::format_duration(cooldown),
-        context.user
+        "{command} {scope:?} rate limit exceeded by {} by {user}",
+        text_utils::format_duration(cooldown)
     );
 
-    Some(cooldown)
+    Some((scope, cooldown))
+}
+
+fn format_rate_limit(language: Language, scope: RateLimitScope, cooldown: u64) -> String {
+    let cooldown = text_utils::format_duration(cooldown);
+    let arguments = [("cooldown", &cooldown as &dyn fmt::Display)];
+
+    match scope {
+        RateLimitScope::User => i18n::format(language, "rate_limit_user", &arguments),
+        RateLimitScope::Chat => i18n::format(language, "rate_limit_chat", &arguments),
+        RateLimitScope::Global | RateLimitScope::Api(_) => {
+            i18n::format(language, "rate_limit_global", &arguments)
+        }
+    }
 }
 
-async fn report_rate_limit(context: &CommandContext, cooldown: u64) -> TdResult<()> {
+async fn report_rate_limit(
+    context: &CommandContext,
+    scope: RateLimitScope,
+    cooldown: u64,
+) -> TdResult<()> {
+    // report exceeded rate limits at most once per 20 seconds
     if context
         .bot_state
-        .rate_limits
+        .rate_limiter
         .lock()
         .unwrap()
-        .rate_limit_exceeded
-        .update_rate_limit(context.user.id, context.message.date)
+        .check(
+            "rate_limit_exceeded",
+            context.user.id,
+            context.message.chat_id,
+            &[RateLimit::user(1, 20)],
+            context.message.date.into(),
+        )
         .is_some()
     {
         return Ok(());
@@ -75,13 +387,7 @@ async fn report_rate_limit(context: &CommandContext, cooldown: u64) -> TdResult<
         .bot_state
         .message_queue
         .wait_for_message(
-            context
-                .reply(format!(
-                    "you can use this command again in {}.",
-                    text_utils::format_duration(cooldown)
-                ))
-                .await?
-                .id,
+            context.reply(format_rate_limit(context.language(), scope, cooldown)).await?.id,
         )
         .await?;
 
@@ -97,21 +403,35 @@ async fn report_command_error(
     context: &CommandContext,
     error: CommandError,
 ) -> TdResult<()> {
-    match error {
-        CommandError::Custom(text) => context.reply(text.into()).await?,
-        CommandError::CustomFormattedText(text) => context.reply_formatted_text(text).await?,
-        CommandError::ArgumentConversion(err) => context.reply(err.to_string()).await?,
+    context.reply_formatted_text(format_command_error(&command, context.language(), error)).await?;
+
+    Ok(())
+}
+
+pub fn format_command_error(
+    command: &impl fmt::Display,
+    language: Language,
+    error: CommandError,
+) -> FormattedText {
+    let text = match error {
+        CommandError::Custom(text) => text.into_owned(),
+        CommandError::CustomFormattedText(text) => return text,
+        CommandError::ArgumentConversion(err) => err.localize(language),
         CommandError::Telegram(err) => {
             log::error!("TDLib error in the {command} command: {}: {}", err.code, err.message);
-            context.reply(format!("sending the message failed ({}) 😔", err.message)).await?
+            i18n::format(language, "send_failed", &[("message", &err.message)])
         }
         CommandError::Server(status_code) => {
-            context
-                .reply(format!(
-                    "an external service used by this command is currently offline ({status_code})."
-                ))
-                .await?
+            i18n::format(language, "server_offline", &[("status", &status_code)])
         }
+        CommandError::Unavailable(unavailable) => i18n::format(
+            language,
+            "service_unavailable",
+            &[
+                ("service", &unavailable.endpoint.service_name()),
+                ("seconds", &unavailable.retry_in.as_secs().max(1)),
+            ],
+        ),
         CommandError::Reqwest(err) => {
             let err = err.without_url();
 
@@ -119,19 +439,55 @@ async fn report_command_error(
                 err.source().map_or_else(|| err.to_string(), |source| format!("{err}: {source}"));
 
             log::error!("HTTP error in the {command} command: {text}");
-            context.reply(text).await?
+            text
         }
-        CommandError::SerdeJson(err) => context.reply(format!("JSON parse error: {err}")).await?,
+        CommandError::SerdeJson(err) => i18n::format(language, "json_error", &[("error", &err)]),
         CommandError::Download(err) => match err {
             DownloadError::RequestError(err) => {
                 log::warn!("cobalt download failed: {err}");
-                context.reply(format!("≫ cobalt download failed: {}", err.without_url())).await?
-            }
-            DownloadError::FilesystemError => {
-                context.reply("failed to save the file to the hard drive.".into()).await?
+                i18n::format(language, "cobalt_failed", &[("error", &err.without_url())])
             }
+            DownloadError::FilesystemError => i18n::text(language, "filesystem_error").into(),
         },
     };
 
-    Ok(())
+    FormattedText { text, ..Default::default() }
+}
+
+#[cfg(test)]
+mod test {
+    use tdlib::enums::ChatMemberStatus;
+    use tdlib::types::ChatMemberStatusMember;
+
+    use super::*;
+    use crate::commands::cancel::Cancel;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_command_error_reply() {
+        let (ctx, client) = test_fixtures::fake_command_context();
+        let command =
+            Arc::new(CommandInstance { command: Box::new(Cancel), rate_limits: Vec::new() });
+
+        dispatch_command(command, String::new(), ctx, false).await;
+
+        assert_eq!(client.sent_texts(), ["you have no running commands in this chat."]);
+    }
+
+    #[tokio::test]
+    async fn test_admin_only_reply() {
+        let (ctx, client) = test_fixtures::fake_command_context();
+        let command =
+            Arc::new(CommandInstance { command: Box::new(Cancel), rate_limits: Vec::new() });
+
+        ctx.bot_state.cache.lock().unwrap().set_member_status(
+            ctx.message.chat_id,
+            ctx.user.id,
+            ChatMemberStatus::Member(ChatMemberStatusMember::default()),
+        );
+
+        dispatch_command(command, String::new(), ctx, true).await;
+
+        assert_eq!(client.sent_texts(), ["only chat admins can use this command."]);
+    }
 }
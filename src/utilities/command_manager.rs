@@ -1,21 +1,29 @@
+use std::collections::HashSet;
 use std::fmt;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use tdlib::types::BotCommand;
 
-use super::rate_limit::RateLimiter;
-use crate::commands::CommandTrait;
+use super::i18n::{self, Language};
+use super::rate_limit::RateLimit;
+use crate::commands::{CommandTrait, MenuScope};
 
 pub type CommandRef = Box<dyn CommandTrait + Send + Sync>;
 
 pub struct CommandInstance {
     pub command: CommandRef,
-    pub rate_limiter: Mutex<RateLimiter<i64>>,
+    pub rate_limits: Vec<RateLimit>,
+}
+
+impl CommandInstance {
+    pub fn name(&self) -> &str {
+        self.command.command_names().first().unwrap()
+    }
 }
 
 impl fmt::Display for CommandInstance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "/{}", self.command.command_names().first().unwrap())
+        write!(f, "/{}", self.name())
     }
 }
 
@@ -29,25 +37,48 @@ impl CommandManager {
     }
 
     pub fn add_command(&mut self, command: Box<impl CommandTrait + Send + Sync + 'static>) {
-        self.commands.push(Arc::new(CommandInstance {
-            rate_limiter: Mutex::new(command.rate_limit()),
-            command,
-        }));
+        self.commands
+            .push(Arc::new(CommandInstance { rate_limits: command.rate_limits(), command }));
     }
 
     pub fn get_command(&self, name: &str) -> Option<Arc<CommandInstance>> {
         self.commands.iter().find(|c| c.command.command_names().contains(&name)).cloned()
     }
 
-    pub fn public_command_list(&self) -> Vec<BotCommand> {
+    /// a command name which only the first command using it can be run with
+    pub fn duplicate_name(&self) -> Option<&str> {
+        let mut names = HashSet::new();
+
+        self.commands
+            .iter()
+            .flat_map(|command| command.command.command_names())
+            .find(|name| !names.insert(*name))
+            .copied()
+    }
+
+    pub fn commands(&self) -> &[Arc<CommandInstance>] {
+        &self.commands
+    }
+
+    pub fn public_command_list(&self, scope: MenuScope, language: Language) -> Vec<BotCommand> {
         self.commands
             .iter()
+            .filter(|c| c.command.menu_scopes().contains(&scope))
             .filter_map(|c| {
                 c.command.description().map(|d| BotCommand {
-                    command: (*c.command.command_names().first().unwrap()).into(),
-                    description: d.into(),
+                    command: c.name().into(),
+                    description: i18n::command_description(language, c.name()).unwrap_or(d).into(),
                 })
             })
             .collect()
     }
+
+    /// the command lists of every menu in every language
+    pub fn command_menus(&self) -> Vec<(MenuScope, Language, Vec<BotCommand>)> {
+        MenuScope::ALL
+            .into_iter()
+            .flat_map(|scope| Language::ALL.map(|language| (scope, language)))
+            .map(|(scope, language)| (scope, language, self.public_command_list(scope, language)))
+            .collect()
+    }
 }
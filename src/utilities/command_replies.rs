@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tdlib::types::Message;
+
+const EDIT_WINDOW: Duration = Duration::from_mins(10);
+/// re-runs aren't rate limited, so they're limited per message instead
+const MAX_RERUNS: u32 = 3;
+
+struct CommandReply {
+    message: Message,
+    reply_id: Option<i64>,
+    rerun: bool,
+    reruns: u32,
+    created_at: Instant,
+}
+
+/// remembers recent command messages and the replies sent to them,
+/// so commands can be re-run when their message is edited
+#[derive(Default)]
+pub struct CommandReplies {
+    commands: HashMap<(i64, i64), CommandReply>,
+}
+
+impl CommandReplies {
+    pub fn insert(&mut self, message: &Message) {
+        self.commands.retain(|_, command| command.created_at.elapsed() < EDIT_WINDOW);
+
+        self.commands.entry((message.chat_id, message.id)).or_insert_with(|| CommandReply {
+            message: message.clone(),
+            reply_id: None,
+            rerun: false,
+            reruns: 0,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// marks the command for a re-run and returns its message, if it was sent
+    /// recently enough and wasn't re-run too many times already
+    pub fn rerun(&mut self, chat_id: i64, message_id: i64) -> Option<Message> {
+        let command = self.commands.get_mut(&(chat_id, message_id))?;
+
+        if command.created_at.elapsed() >= EDIT_WINDOW || command.reruns >= MAX_RERUNS {
+            return None;
+        }
+
+        command.rerun = true;
+        command.reruns += 1;
+
+        Some(command.message.clone())
+    }
+
+    /// returns the reply that should be edited instead of sending a new one
+    pub fn take_reply_to_edit(&mut self, chat_id: i64, message_id: i64) -> Option<i64> {
+        let command = self.commands.get_mut(&(chat_id, message_id))?;
+
+        if !command.rerun {
+            return None;
+        }
+
+        command.rerun = false;
+        command.reply_id
+    }
+
+    pub fn set_reply(&mut self, chat_id: i64, message_id: i64, reply_id: i64) {
+        if let Some(command) = self.commands.get_mut(&(chat_id, message_id)) {
+            command.reply_id.get_or_insert(reply_id);
+        }
+    }
+
+    pub fn replace_reply(&mut self, chat_id: i64, message_id: i64, reply_id: i64) {
+        if let Some(command) = self.commands.get_mut(&(chat_id, message_id)) {
+            command.reply_id = Some(reply_id);
+        }
+    }
+
+    pub fn message_sent(&mut self, chat_id: i64, old_message_id: i64, new_message_id: i64) {
+        for ((command_chat_id, _), command) in &mut self.commands {
+            if *command_chat_id == chat_id && command.reply_id == Some(old_message_id) {
+                command.reply_id = Some(new_message_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures;
+
+    #[test]
+    fn test_rerun_limit() {
+        let mut command_replies = CommandReplies::default();
+        command_replies.insert(&test_fixtures::text_message(1, 2, "/ping"));
+
+        for _ in 0..MAX_RERUNS {
+            assert!(command_replies.rerun(1, 2).is_some());
+        }
+
+        assert!(command_replies.rerun(1, 2).is_none());
+        assert!(command_replies.rerun(1, 3).is_none());
+    }
+}
@@ -1,36 +1,61 @@
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{self, BufReader, BufWriter};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
-const FILENAME: &str = "config.dat";
+use super::persistence::Persistent;
 
 #[derive(Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub markov_chain_learning: HashSet<i64>,
+    pub disabled_commands: HashMap<i64, HashSet<String>>,
+    pub admin_only_commands: HashMap<i64, HashSet<String>>,
+    pub blocked_users: HashSet<i64>,
+    /// language codes overriding the languages of the users in each chat
+    pub chat_languages: HashMap<i64, String>,
+    pub command_aliases: HashMap<i64, BTreeMap<String, CommandAlias>>,
 }
 
-impl Config {
-    pub fn load() -> io::Result<Self> {
-        let path = Path::new(FILENAME);
-
-        if path.exists() {
-            log::debug!("loading bot config from drive");
-            Ok(rmp_serde::decode::from_read(BufReader::new(File::open(path)?)).unwrap())
-        } else {
-            log::debug!("creating default bot config");
-            Ok(Self::default())
+/// a custom command running another command with arguments prepended
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CommandAlias {
+    pub command: String,
+    pub arguments: String,
+}
+
+impl CommandAlias {
+    pub fn expand(&self, arguments: &str) -> String {
+        match (self.arguments.is_empty(), arguments.is_empty()) {
+            (_, true) => self.arguments.clone(),
+            (true, false) => arguments.into(),
+            (false, false) => format!("{} {arguments}", self.arguments),
         }
     }
+}
+
+impl Persistent for Config {
+    const FILENAME: Option<&'static str> = Some("config.dat");
+    const KEY: &'static str = "config";
+    const NAME: &'static str = "bot config";
+    const VERSION: u32 = 1;
+
+    fn create() -> Self {
+        Self::default()
+    }
+}
+
+impl Config {
+    pub fn is_command_disabled(&self, chat_id: i64, command_name: &str) -> bool {
+        self.disabled_commands.get(&chat_id).is_some_and(|commands| commands.contains(command_name))
+    }
 
-    pub fn save(&self) -> io::Result<()> {
-        log::debug!("saving bot config to drive");
-        let file = File::options().write(true).truncate(true).create(true).open(FILENAME)?;
-        rmp_serde::encode::write_named(&mut BufWriter::new(file), self).unwrap();
+    pub fn command_alias(&self, chat_id: i64, name: &str) -> Option<&CommandAlias> {
+        self.command_aliases.get(&chat_id)?.get(name)
+    }
 
-        Ok(())
+    pub fn is_command_admin_only(&self, chat_id: i64, command_name: &str) -> bool {
+        self.admin_only_commands
+            .get(&chat_id)
+            .is_some_and(|commands| commands.contains(command_name))
     }
 }
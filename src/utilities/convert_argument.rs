@@ -1,14 +1,11 @@
 use std::borrow::Cow;
 use std::collections::VecDeque;
-use std::fmt;
 
 use async_trait::async_trait;
-use tdlib::enums::{
-    Message, MessageContent, MessageOrigin, MessageReplyTo, MessageSender, UserType,
-};
-use tdlib::functions;
+use tdlib::enums::{MessageContent, MessageOrigin, MessageReplyTo, MessageSender, UserType};
 
 use super::command_context::CommandContext;
+use super::i18n::{self, Language};
 use super::parsed_command::ParsedCommand;
 use super::telegram_utils;
 use crate::bot::TdError;
@@ -20,12 +17,18 @@ pub enum ConversionError {
     TdError(TdError),
 }
 
-impl fmt::Display for ConversionError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl ConversionError {
+    pub fn localize(&self, language: Language) -> String {
         match self {
-            Self::MissingArgument => write!(f, "missing command argument"),
-            Self::BadArgument(reason) => write!(f, "bad command argument: {reason}"),
-            Self::TdError(err) => write!(f, "error {}: {}", err.code, err.message),
+            Self::MissingArgument => i18n::text(language, "missing_argument").into(),
+            Self::BadArgument(reason) => {
+                i18n::format(language, "bad_argument", &[("reason", reason)])
+            }
+            Self::TdError(err) => i18n::format(
+                language,
+                "telegram_error",
+                &[("code", &err.code), ("message", &err.message)],
+            ),
         }
     }
 }
@@ -105,10 +108,11 @@ impl ConvertArgument for Reply {
         let content = if let Some(content) = reply.content.as_ref() {
             Cow::Borrowed(content)
         } else {
-            let Message::Message(message) =
-                functions::get_replied_message(ctx.message.chat_id, ctx.message.id, ctx.client_id)
-                    .await
-                    .map_err(ConversionError::TdError)?;
+            let message = ctx
+                .client
+                .get_replied_message(ctx.message.chat_id, ctx.message.id)
+                .await
+                .map_err(ConversionError::TdError)?;
 
             Cow::Owned(message.content)
         };
@@ -203,14 +207,12 @@ impl ConvertArgument for ReplyChain {
                 break;
             };
 
-            message = match functions::get_replied_message(
-                current_message.chat_id,
-                current_message.id,
-                ctx.client_id,
-            )
-            .await
+            message = match ctx
+                .client
+                .get_replied_message(current_message.chat_id, current_message.id)
+                .await
             {
-                Ok(Message::Message(message)) => Some(message),
+                Ok(message) => Some(message),
                 Err(err) => {
                     if err.code == 404 {
                         None
@@ -303,9 +305,51 @@ impl ConvertArgument for bool {
 
 #[cfg(test)]
 mod test {
+    use tdlib::types::MessageReplyToMessage;
+
     use super::*;
     use crate::utilities::test_fixtures;
 
+    fn reply_to(chat_id: i64, message_id: i64) -> Option<MessageReplyTo> {
+        Some(MessageReplyTo::Message(MessageReplyToMessage {
+            chat_id,
+            message_id,
+            ..Default::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_reply_converter() {
+        let (mut ctx, client) = test_fixtures::fake_command_context();
+
+        ctx.message.reply_to = reply_to(0, 1);
+        let result = Reply::convert(&ctx, "").await;
+        assert!(matches!(result, Err(ConversionError::TdError(TdError { code: 404, .. }))));
+
+        client.set_replied_message(0, 0, test_fixtures::text_message(0, 1, "replied text"));
+        let (Reply(argument), rest) = Reply::convert(&ctx, "foo").await.unwrap();
+        assert_eq!(argument, "replied text");
+        assert_eq!(rest, "foo");
+    }
+
+    #[tokio::test]
+    async fn test_reply_chain_converter() {
+        let (mut ctx, client) = test_fixtures::fake_command_context();
+
+        let mut first = test_fixtures::text_message(0, 1, "first");
+        first.reply_to = reply_to(0, 2);
+        client.set_replied_message(0, 1, test_fixtures::text_message(0, 2, "second"));
+        client.set_replied_message(0, 0, first);
+        ctx.message.reply_to = reply_to(0, 1);
+
+        let (ReplyChain(messages), rest) = ReplyChain::convert(&ctx, "third").await.unwrap();
+        assert_eq!(rest, "");
+
+        let texts = messages.iter().map(|message| message.text.as_deref()).collect::<Vec<_>>();
+        assert_eq!(texts, [Some("second"), Some("first"), Some("third")]);
+        assert!(messages.iter().all(|message| !message.bot_author));
+    }
+
     #[tokio::test]
     async fn test_string_converter() {
         let ctx = test_fixtures::command_context();
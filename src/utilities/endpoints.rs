@@ -0,0 +1,184 @@
+//! base URLs of the external APIs, which can be overridden in settings to point
+//! the bot at a self-hosted mirror or a local stand-in server
+
+#[cfg(test)]
+use std::cell::RefCell;
+#[cfg(test)]
+use std::collections::HashMap;
+
+use super::settings;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Endpoint {
+    Coinranking,
+    DifferentDimensionMe,
+    Eurofxref,
+    Fal,
+    Google,
+    GoogleAiStudio,
+    Groq,
+    KiwiFarms,
+    Mathjs,
+    Microlink,
+    Moveit,
+    OpenRouter,
+    Petittube,
+    Poligon,
+    Polymarket,
+    StableHorde,
+    Translate,
+    UrbanDictionary,
+    UrbanDictionaryApi,
+    Urbansharing,
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_OVERRIDES: RefCell<HashMap<Endpoint, String>> = RefCell::default();
+}
+
+impl Endpoint {
+    pub const ALL: [Self; 20] = [
+        Self::Coinranking,
+        Self::DifferentDimensionMe,
+        Self::Eurofxref,
+        Self::Fal,
+        Self::Google,
+        Self::GoogleAiStudio,
+        Self::Groq,
+        Self::KiwiFarms,
+        Self::Mathjs,
+        Self::Microlink,
+        Self::Moveit,
+        Self::OpenRouter,
+        Self::Petittube,
+        Self::Poligon,
+        Self::Polymarket,
+        Self::StableHorde,
+        Self::Translate,
+        Self::UrbanDictionary,
+        Self::UrbanDictionaryApi,
+        Self::Urbansharing,
+    ];
+
+    /// key of the endpoint in the `endpoints` setting
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Coinranking => "coinranking",
+            Self::DifferentDimensionMe => "different_dimension_me",
+            Self::Eurofxref => "eurofxref",
+            Self::Fal => "fal",
+            Self::Google => "google",
+            Self::GoogleAiStudio => "google_aistudio",
+            Self::Groq => "groq",
+            Self::KiwiFarms => "kiwifarms",
+            Self::Mathjs => "mathjs",
+            Self::Microlink => "microlink",
+            Self::Moveit => "moveit",
+            Self::OpenRouter => "openrouter",
+            Self::Petittube => "petittube",
+            Self::Poligon => "poligon",
+            Self::Polymarket => "polymarket",
+            Self::StableHorde => "stablehorde",
+            Self::Translate => "translate",
+            Self::UrbanDictionary => "urbandictionary",
+            Self::UrbanDictionaryApi => "urbandictionary_api",
+            Self::Urbansharing => "urbansharing",
+        }
+    }
+
+    /// name of the service shown to users
+    pub const fn service_name(self) -> &'static str {
+        match self {
+            Self::Coinranking => "Coinranking",
+            Self::DifferentDimensionMe => "Different Dimension Me",
+            Self::Eurofxref => "the ECB exchange rate service",
+            Self::Fal => "fal.ai",
+            Self::Google | Self::Translate => "Google",
+            Self::GoogleAiStudio => "Google AI Studio",
+            Self::Groq => "Groq",
+            Self::KiwiFarms => "Kiwi Farms",
+            Self::Mathjs => "math.js",
+            Self::Microlink => "Microlink",
+            Self::Moveit => "the joke service",
+            Self::OpenRouter => "OpenRouter",
+            Self::Petittube => "Petittube",
+            Self::Poligon => "Poligon",
+            Self::Polymarket => "Polymarket",
+            Self::StableHorde => "Stable Horde",
+            Self::UrbanDictionary | Self::UrbanDictionaryApi => "Urban Dictionary",
+            Self::Urbansharing => "Urbansharing",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|endpoint| endpoint.name() == name)
+    }
+
+    const fn default_url(self) -> &'static str {
+        match self {
+            Self::Coinranking => "https://api.coinranking.com/v2",
+            Self::DifferentDimensionMe => "https://ai.tu.qq.com",
+            Self::Eurofxref => "https://www.ecb.europa.eu/stats/eurofxref",
+            Self::Fal => "https://fal.run",
+            Self::Google => "https://google.com",
+            Self::GoogleAiStudio => "https://generativelanguage.googleapis.com",
+            Self::Groq => "https://api.groq.com/openai/v1",
+            Self::KiwiFarms => "https://kiwifarms.st",
+            Self::Mathjs => "https://api.mathjs.org/v4",
+            Self::Microlink => "https://api.microlink.io",
+            Self::Moveit => "https://qdpnjkjlql.execute-api.eu-central-1.amazonaws.com",
+            Self::OpenRouter => "https://openrouter.ai/api/v1",
+            Self::Petittube => "https://petittube.com",
+            Self::Poligon => "https://astolfo.poligon.lgbt/api",
+            Self::Polymarket => "https://polymarket.com/api",
+            Self::StableHorde => "https://stablehorde.net/api/v2",
+            Self::Translate => "https://translate.google.com",
+            Self::UrbanDictionary => "https://www.urbandictionary.com",
+            Self::UrbanDictionaryApi => "https://api.urbandictionary.com/v0",
+            Self::Urbansharing => "https://core.urbansharing.com/public/api/v1",
+        }
+    }
+
+    /// the configured base URL, without a trailing slash
+    pub fn base_url(self) -> String {
+        #[cfg(test)]
+        if let Some(url) = TEST_OVERRIDES.with_borrow(|overrides| overrides.get(&self).cloned()) {
+            return url;
+        }
+
+        settings::get()
+            .endpoints
+            .get(self.name())
+            .map_or_else(|| self.default_url().into(), |url| url.trim_end_matches('/').into())
+    }
+
+    /// joins the base URL with a path starting with a slash
+    pub fn url(self, path: &str) -> String {
+        format!("{}{path}", self.base_url())
+    }
+
+    /// overrides the base URL for requests made on the current thread
+    #[cfg(test)]
+    pub fn set_test_override(self, url: String) {
+        TEST_OVERRIDES.with_borrow_mut(|overrides| overrides.insert(self, url));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Endpoint;
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(Endpoint::Mathjs.url("/"), "https://api.mathjs.org/v4/");
+        assert_eq!(Endpoint::from_name("stablehorde"), Some(Endpoint::StableHorde));
+        assert_eq!(Endpoint::from_name("unknown"), None);
+
+        Endpoint::Polymarket.set_test_override("http://127.0.0.1:8080".into());
+        assert_eq!(
+            Endpoint::Polymarket.url("/events/global"),
+            "http://127.0.0.1:8080/events/global"
+        );
+    }
+}
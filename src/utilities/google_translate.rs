@@ -209,12 +209,8 @@ pub fn get_language_name(language_code: &str) -> Option<&str> {
 #[derive(PartialEq, Eq)]
 pub struct Language(pub &'static str);
 
-#[async_trait]
-impl ConvertArgument for Language {
-    async fn convert<'a>(
-        _: &CommandContext,
-        arguments: &'a str,
-    ) -> Result<(Self, &'a str), ConversionError> {
+impl Language {
+    pub fn parse(arguments: &str) -> Result<(Self, &str), ConversionError> {
         let arguments = arguments.trim_ascii_start();
 
         if arguments.is_empty() {
@@ -238,31 +234,45 @@ impl ConvertArgument for Language {
     }
 }
 
-pub struct SourceTargetLanguages(pub Option<&'static str>, pub Cow<'static, str>);
-
 #[async_trait]
-impl ConvertArgument for SourceTargetLanguages {
+impl ConvertArgument for Language {
     async fn convert<'a>(
-        ctx: &CommandContext,
+        _: &CommandContext,
         arguments: &'a str,
     ) -> Result<(Self, &'a str), ConversionError> {
-        let Some((Language(first_language), rest)) = Language::convert(ctx, arguments).await.ok()
-        else {
-            let target_language = if ctx.user.language_code.is_empty() {
+        Self::parse(arguments)
+    }
+}
+
+pub struct SourceTargetLanguages(pub Option<&'static str>, pub Cow<'static, str>);
+
+impl SourceTargetLanguages {
+    pub fn parse<'a>(arguments: &'a str, user_language_code: &str) -> (Self, &'a str) {
+        let Ok((Language(first_language), rest)) = Language::parse(arguments) else {
+            let target_language = if user_language_code.is_empty() {
                 Cow::Borrowed("en")
             } else {
-                Cow::Owned(ctx.user.language_code.clone())
+                Cow::Owned(user_language_code.into())
             };
 
-            return Ok((Self(None, target_language), arguments));
+            return (Self(None, target_language), arguments);
         };
 
-        let Some((Language(second_language), rest)) = Language::convert(ctx, rest).await.ok()
-        else {
-            return Ok((Self(None, Cow::Borrowed(first_language)), rest));
+        let Ok((Language(second_language), rest)) = Language::parse(rest) else {
+            return (Self(None, Cow::Borrowed(first_language)), rest);
         };
 
-        Ok((Self(Some(first_language), Cow::Borrowed(second_language)), rest))
+        (Self(Some(first_language), Cow::Borrowed(second_language)), rest)
+    }
+}
+
+#[async_trait]
+impl ConvertArgument for SourceTargetLanguages {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        Ok(Self::parse(arguments, &ctx.user.language_code))
     }
 }
 
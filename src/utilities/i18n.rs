@@ -0,0 +1,630 @@
+use std::fmt::{Display, Write};
+
+use super::bot_state::BotState;
+use super::cache::CompactUser;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Language {
+    English,
+    Polish,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::Polish];
+
+    /// parses an IETF language tag, like the `language_code` of Telegram users
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.split(['-', '_']).next().unwrap();
+
+        Self::ALL.into_iter().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Polish => "pl",
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Polish => "polski",
+        }
+    }
+
+    const fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => ENGLISH,
+            Self::Polish => POLISH,
+        }
+    }
+
+    /// translated descriptions of the built-in commands, keyed by their main
+    /// names
+    const fn command_catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => &[],
+            Self::Polish => POLISH_COMMANDS,
+        }
+    }
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("missing_argument", "missing command argument"),
+    ("bad_argument", "bad command argument: {reason}"),
+    ("telegram_error", "error {code}: {message}"),
+    ("rate_limit_user", "you can use this command again in {cooldown}."),
+    ("rate_limit_chat", "this command can be used in this chat again in {cooldown}."),
+    ("rate_limit_global", "this command is used too often, try again in {cooldown}."),
+    ("queue_position", "waiting in queue (position {position})…"),
+    ("button_forbidden", "you can't use this button."),
+    ("button_admin_only", "only chat admins can use this button."),
+    ("command_admin_only", "only chat admins can use this command."),
+    ("send_failed", "sending the message failed ({message}) 😔"),
+    ("server_offline", "an external service used by this command is currently offline ({status})."),
+    (
+        "service_unavailable",
+        "{service} seems to be down right now. try again in {seconds} seconds.",
+    ),
+    ("json_error", "JSON parse error: {error}"),
+    ("cobalt_failed", "≫ cobalt download failed: {error}"),
+    ("filesystem_error", "failed to save the file to the hard drive."),
+    ("no_running_commands", "you have no running commands in this chat."),
+    ("cancelled_commands_one", "cancelled 1 command."),
+    ("cancelled_commands_many", "cancelled {count} commands."),
+    ("language_set", "bot language in this chat is now {language}."),
+    ("language_reset", "bot language in this chat now follows the settings of each user."),
+    ("unknown_language", "unknown language. available languages: {languages}."),
+    ("unknown_command", "unknown command."),
+    ("category_ai", "AI chat"),
+    ("category_images", "image generation"),
+    ("category_media", "media"),
+    ("category_text", "text and information"),
+    ("category_fun", "fun"),
+    ("category_bot", "bot"),
+    ("category_other", "other"),
+    ("help_usage_hint", "use {command} to see how to use a command."),
+    ("help_aliases", "aliases: "),
+    ("help_arguments", "arguments:"),
+    ("help_examples", "examples:"),
+    ("settings_list", "list of available settings:"),
+    ("unknown_setting", "unknown setting name. available settings include:"),
+    ("markov_learning_enabled", "Markov chain will now learn from chat messages."),
+    ("markov_learning_already_enabled", "Markov chain learning was already enabled."),
+    ("markov_learning_disabled", "Markov chain won't learn from chat messages anymore."),
+    ("markov_learning_already_disabled", "Markov chain learning was already disabled."),
+    ("command_enabled_now", "is now enabled in this chat."),
+    ("command_enabled_already", "was already enabled in this chat."),
+    ("command_disabled_now", "is now disabled in this chat."),
+    ("command_disabled_already", "was already disabled in this chat."),
+    ("command_admin_only_now", "can now only be used by chat admins."),
+    ("command_admin_only_already", "could already only be used by chat admins."),
+    ("command_everyone_now", "can now be used by everyone."),
+    ("command_everyone_already", "could already be used by everyone."),
+    ("command_not_restrictable", "bot commands cannot be disabled or restricted."),
+    ("setting_groups_only", "this setting can be only set in groups."),
+    ("setting_requires_change_info", "this setting requires the Change Group Info permission."),
+    ("no_prompt", "no prompt provided."),
+    ("no_prompt_or_file", "no prompt or file provided."),
+    ("prompt_too_long", "this prompt is too long (>1024)."),
+    ("prompt_too_many_lines", "this prompt has too many lines (>8)."),
+    ("file_too_large", "files cannot be larger than {size}."),
+    ("image_too_large", "the image cannot be larger than {size}."),
+    ("image_required", "send or reply to an image."),
+    (
+        "image_censored",
+        "Xi Jinping does not approve of this image and has censored it (error {code}: {message})",
+    ),
+    ("open_full_image", "open full image"),
+    ("api_error", "error {code}: {message}"),
+    ("no_response", "no response generated."),
+    ("no_text_generated", "no text was generated."),
+    ("no_text_generated_marker", "[no text generated]"),
+    ("unsupported_response_part", "[unsupported response part]"),
+    ("finish_reason", "[finish reason: {reason}]"),
+    ("thinking_hidden", "[{count} thinking chars hidden]"),
+    ("gemini_blocked", "request blocked by Google."),
+    ("gemini_blocked_reasons", "request blocked by Google: {reasons}."),
+    ("generation_failed", "the generation failed."),
+    ("generation_timed_out", "the generation timed out."),
+    ("no_images_generated", "no images were successfully generated."),
+    ("no_online_workers", "there are no online workers for the requested model."),
+    ("stablehorde_queue_position", "queue position: {position}"),
+    ("stablehorde_credit", "generated thanks to Stable Horde"),
+    ("stablehorde_volunteers", "Stable Horde is run by volunteers. to make wait times shorter, "),
+    ("stablehorde_join", "consider joining yourself"),
+    ("generating", "generating "),
+    ("generated", "generated "),
+    ("generated_in", " in "),
+    ("generated_by", " by "),
+    ("generated_in_seconds", " in {seconds}s. "),
+    ("download", "download"),
+    ("download_links", ".\ndownload: "),
+    ("translate_no_text", "type the text to translate."),
+    ("charinfo_no_text", "type the characters to look up."),
+    ("charinfo_title", "Unicode character names"),
+    ("invalid_amount", "{amount} is not a valid amount."),
+    ("unknown_source_currency", "could not find source currency "),
+    ("unknown_target_currency", "could not find target currency "),
+    ("no_definitions", "sorry, there are no definitions for this word."),
+    ("no_autocompletions", "no autocompletions"),
+    ("no_results", "no results found."),
+    ("markov_learning_required", "a chat admin has to enable Markov chain learning with "),
+    ("livestream", "cannot download livestreams."),
+    ("upcoming_livestream", "this is an upcoming livestream."),
+    ("livestream_processing", "this livestream is still processing."),
+    ("media_too_long", "cannot download media longer than {duration}."),
+    ("media_too_large", "cannot download media larger than {size}."),
+    ("media_size_unknown", "cannot download media, because duration and filesize are unknown."),
+    ("downloading", "downloading "),
+    ("downloading_from", "downloading from "),
+    ("uploading", "uploading…"),
+    ("no_cobalt_instances", "no cobalt instances are configured."),
+    ("cobalt_download_link", "download using cobalt.tools"),
+    ("kiwifarms_yes", "yes 🤬"),
+    ("kiwifarms_status", "{status} no"),
+    ("kiwifarms_error", "no ({error})"),
+    (
+        "start_intro",
+        concat!(
+            "hello! this bot contains many useful and fun commands like:\n",
+            "• /gemini (/g) – talk to Google Gemini\n",
+            "• /cobalt_download (/dl), /cobalt_download_audio (/dla) – download media using ",
+        ),
+    ),
+    ("start_yt_dlp", "\n• /yt_dlp (/yt), /yt_dlp_audio (/yta) – download media using "),
+    (
+        "start_commands",
+        concat!(
+            "\n• /translate (/tr) – translate text using Google Translate\n",
+            "• /convert (/c) – convert between popular currencies and cryptocurrencies\n",
+            "• /screenshot (/ss) – screenshot websites\n",
+            "• /urbandictionary (/ud) – get slang term definitions\n",
+            "• /charinfo (/ch) – see Unicode character names\n",
+            "and more! use /help to see all of them.\n\n",
+            "you can reply to other messages ",
+            "or quote their parts to provide arguments. ",
+            "this is my open-source hobby project, made using ",
+        ),
+    ),
+    ("start_and", " and "),
+    ("start_outro", ". not everything will always work. star the repository on "),
+    ("alias_builtin", "built-in commands can't be redefined."),
+    ("alias_limit", "a chat can't have more than {limit} aliases."),
+    ("alias_defined", " now runs "),
+    ("no_such_alias", "there is no such alias in this chat."),
+    ("alias_removed", " was removed."),
+    ("no_aliases", "this chat has no aliases. define one with /alias name = /command."),
+    ("alias_list", "aliases in this chat:"),
+    ("alias_usage", "use /alias name = /command arguments."),
+    (
+        "alias_invalid_name",
+        "alias names can only contain up to {limit} letters, digits and underscores.",
+    ),
+    ("alias_missing_command", "specify the command to run."),
+    ("note_limit", "a chat can't have more than {limit} notes."),
+    ("note", "note "),
+    ("note_saved", " was saved."),
+    ("note_replaced", " was replaced."),
+    ("note_removed", " was removed."),
+    ("no_such_note", "there is no such note in this chat."),
+    ("no_notes", "this chat has no notes. save one with /save <name>."),
+    ("note_list", "notes in this chat:"),
+    ("note_list_hint", "\n\nshow one with "),
+    ("note_name_too_long", "note names can't be longer than {limit} characters."),
+    ("note_missing_text", "write the note text or reply to a message."),
+    ("note_unsupported_message", "this message can't be saved as a note."),
+];
+
+const POLISH: &[(&str, &str)] = &[
+    ("missing_argument", "brak argumentu polecenia"),
+    ("bad_argument", "nieprawidłowy argument polecenia: {reason}"),
+    ("telegram_error", "błąd {code}: {message}"),
+    ("rate_limit_user", "możesz ponownie użyć tego polecenia za {cooldown}."),
+    ("rate_limit_chat", "tego polecenia można ponownie użyć na tym czacie za {cooldown}."),
+    ("rate_limit_global", "to polecenie jest używane zbyt często, spróbuj ponownie za {cooldown}."),
+    ("queue_position", "oczekiwanie w kolejce (pozycja {position})…"),
+    ("button_forbidden", "nie możesz użyć tego przycisku."),
+    ("button_admin_only", "tylko administratorzy czatu mogą użyć tego przycisku."),
+    ("command_admin_only", "tylko administratorzy czatu mogą użyć tego polecenia."),
+    ("send_failed", "wysłanie wiadomości nie powiodło się ({message}) 😔"),
+    ("server_offline", "zewnętrzna usługa używana przez to polecenie jest niedostępna ({status})."),
+    ("service_unavailable", "{service} chyba teraz nie działa. spróbuj ponownie za {seconds} s."),
+    ("json_error", "błąd parsowania JSON: {error}"),
+    ("cobalt_failed", "≫ pobieranie przez cobalt nie powiodło się: {error}"),
+    ("filesystem_error", "nie udało się zapisać pliku na dysku."),
+    ("no_running_commands", "nie masz żadnych uruchomionych poleceń na tym czacie."),
+    ("cancelled_commands_one", "anulowano 1 polecenie."),
+    ("cancelled_commands_few", "anulowano {count} polecenia."),
+    ("cancelled_commands_many", "anulowano {count} poleceń."),
+    ("language_set", "język bota na tym czacie to teraz {language}."),
+    ("language_reset", "język bota na tym czacie zależy teraz od ustawień każdego użytkownika."),
+    ("unknown_language", "nieznany język. dostępne języki: {languages}."),
+    ("unknown_command", "nieznane polecenie."),
+    ("category_ai", "czat z AI"),
+    ("category_images", "generowanie obrazów"),
+    ("category_media", "multimedia"),
+    ("category_text", "tekst i informacje"),
+    ("category_fun", "rozrywka"),
+    ("category_bot", "bot"),
+    ("category_other", "inne"),
+    ("help_usage_hint", "użyj {command}, aby zobaczyć, jak używać polecenia."),
+    ("help_aliases", "aliasy: "),
+    ("help_arguments", "argumenty:"),
+    ("help_examples", "przykłady:"),
+    ("settings_list", "lista dostępnych ustawień:"),
+    ("unknown_setting", "nieznana nazwa ustawienia. dostępne ustawienia to:"),
+    ("markov_learning_enabled", "łańcuch Markowa będzie teraz uczył się z wiadomości na czacie."),
+    ("markov_learning_already_enabled", "nauka łańcucha Markowa była już włączona."),
+    (
+        "markov_learning_disabled",
+        "łańcuch Markowa nie będzie już uczył się z wiadomości na czacie.",
+    ),
+    ("markov_learning_already_disabled", "nauka łańcucha Markowa była już wyłączona."),
+    ("command_enabled_now", "jest teraz włączone na tym czacie."),
+    ("command_enabled_already", "było już włączone na tym czacie."),
+    ("command_disabled_now", "jest teraz wyłączone na tym czacie."),
+    ("command_disabled_already", "było już wyłączone na tym czacie."),
+    ("command_admin_only_now", "może być teraz używane tylko przez administratorów czatu."),
+    ("command_admin_only_already", "mogło już być używane tylko przez administratorów czatu."),
+    ("command_everyone_now", "może być teraz używane przez wszystkich."),
+    ("command_everyone_already", "mogło już być używane przez wszystkich."),
+    ("command_not_restrictable", "poleceń bota nie można wyłączyć ani ograniczyć."),
+    ("setting_groups_only", "to ustawienie można zmienić tylko w grupach."),
+    (
+        "setting_requires_change_info",
+        "to ustawienie wymaga uprawnienia do zmiany informacji o grupie.",
+    ),
+    ("no_prompt", "nie podano promptu."),
+    ("no_prompt_or_file", "nie podano promptu ani pliku."),
+    ("prompt_too_long", "ten prompt jest za długi (>1024)."),
+    ("prompt_too_many_lines", "ten prompt ma za dużo linii (>8)."),
+    ("file_too_large", "pliki nie mogą być większe niż {size}."),
+    ("image_too_large", "obraz nie może być większy niż {size}."),
+    ("image_required", "wyślij obraz lub odpowiedz na wiadomość z obrazem."),
+    (
+        "image_censored",
+        "Xi Jinping nie akceptuje tego obrazu i go ocenzurował (błąd {code}: {message})",
+    ),
+    ("open_full_image", "otwórz pełny obraz"),
+    ("api_error", "błąd {code}: {message}"),
+    ("no_response", "nie wygenerowano odpowiedzi."),
+    ("no_text_generated", "nie wygenerowano tekstu."),
+    ("no_text_generated_marker", "[nie wygenerowano tekstu]"),
+    ("unsupported_response_part", "[nieobsługiwana część odpowiedzi]"),
+    ("finish_reason", "[powód zakończenia: {reason}]"),
+    ("thinking_hidden", "[ukryto znaki rozumowania: {count}]"),
+    ("gemini_blocked", "zapytanie zablokowane przez Google."),
+    ("gemini_blocked_reasons", "zapytanie zablokowane przez Google: {reasons}."),
+    ("generation_failed", "generowanie się nie powiodło."),
+    ("generation_timed_out", "przekroczono limit czasu generowania."),
+    ("no_images_generated", "nie udało się wygenerować żadnego obrazu."),
+    ("no_online_workers", "brak dostępnych workerów dla wybranego modelu."),
+    ("stablehorde_queue_position", "pozycja w kolejce: {position}"),
+    ("stablehorde_credit", "wygenerowano dzięki Stable Horde"),
+    (
+        "stablehorde_volunteers",
+        "Stable Horde jest prowadzone przez wolontariuszy. aby skrócić czas oczekiwania, ",
+    ),
+    ("stablehorde_join", "rozważ dołączenie"),
+    ("generating", "generowanie "),
+    ("generated", "wygenerowano "),
+    ("generated_in", " w "),
+    ("generated_by", " przez "),
+    ("generated_in_seconds", " w {seconds}s. "),
+    ("download", "pobierz"),
+    ("download_links", ".\npobierz: "),
+    ("translate_no_text", "wpisz tekst do przetłumaczenia."),
+    ("charinfo_no_text", "wpisz znaki do sprawdzenia."),
+    ("charinfo_title", "nazwy znaków Unicode"),
+    ("invalid_amount", "{amount} nie jest poprawną kwotą."),
+    ("unknown_source_currency", "nie znaleziono waluty źródłowej "),
+    ("unknown_target_currency", "nie znaleziono waluty docelowej "),
+    ("no_definitions", "niestety, nie ma definicji tego słowa."),
+    ("no_autocompletions", "brak podpowiedzi"),
+    ("no_results", "nie znaleziono wyników."),
+    (
+        "markov_learning_required",
+        "administrator czatu musi włączyć naukę łańcucha Markowa poleceniem ",
+    ),
+    ("livestream", "nie można pobierać transmisji na żywo."),
+    ("upcoming_livestream", "ta transmisja na żywo jeszcze się nie zaczęła."),
+    ("livestream_processing", "ta transmisja na żywo jest jeszcze przetwarzana."),
+    ("media_too_long", "nie można pobrać multimediów dłuższych niż {duration}."),
+    ("media_too_large", "nie można pobrać multimediów większych niż {size}."),
+    ("media_size_unknown", "nie można pobrać multimediów, bo ich długość i rozmiar są nieznane."),
+    ("downloading", "pobieranie "),
+    ("downloading_from", "pobieranie z "),
+    ("uploading", "wysyłanie…"),
+    ("no_cobalt_instances", "nie skonfigurowano żadnych instancji cobalt."),
+    ("cobalt_download_link", "pobierz przez cobalt.tools"),
+    ("kiwifarms_yes", "tak 🤬"),
+    ("kiwifarms_status", "{status} nie"),
+    ("kiwifarms_error", "nie ({error})"),
+    (
+        "start_intro",
+        concat!(
+            "cześć! ten bot ma wiele przydatnych i zabawnych poleceń, na przykład:\n",
+            "• /gemini (/g) – rozmowa z Google Gemini\n",
+            "• /cobalt_download (/dl), /cobalt_download_audio (/dla) – ",
+            "pobieranie multimediów przez ",
+        ),
+    ),
+    ("start_yt_dlp", "\n• /yt_dlp (/yt), /yt_dlp_audio (/yta) – pobieranie multimediów przez "),
+    (
+        "start_commands",
+        concat!(
+            "\n• /translate (/tr) – tłumaczenie tekstu przez Tłumacza Google\n",
+            "• /convert (/c) – przeliczanie popularnych walut i kryptowalut\n",
+            "• /screenshot (/ss) – zrzuty ekranu stron internetowych\n",
+            "• /urbandictionary (/ud) – definicje słów slangowych\n",
+            "• /charinfo (/ch) – nazwy znaków Unicode\n",
+            "i wiele więcej! użyj /help, żeby zobaczyć wszystkie.\n\n",
+            "możesz odpowiedzieć na inne wiadomości ",
+            "lub zacytować ich fragmenty, żeby podać argumenty. ",
+            "to mój otwartoźródłowy projekt hobbystyczny, stworzony przy użyciu ",
+        ),
+    ),
+    ("start_and", " i "),
+    ("start_outro", ". nie wszystko zawsze będzie działać. daj gwiazdkę repozytorium na "),
+    ("alias_builtin", "wbudowanych poleceń nie można zdefiniować na nowo."),
+    ("alias_limit", "czat nie może mieć więcej niż {limit} aliasów."),
+    ("alias_defined", " uruchamia teraz "),
+    ("no_such_alias", "na tym czacie nie ma takiego aliasu."),
+    ("alias_removed", " został usunięty."),
+    (
+        "no_aliases",
+        "ten czat nie ma aliasów. zdefiniuj jeden poleceniem /alias nazwa = /polecenie.",
+    ),
+    ("alias_list", "aliasy na tym czacie:"),
+    ("alias_usage", "użyj /alias nazwa = /polecenie argumenty."),
+    ("alias_invalid_name", "nazwy aliasów mogą zawierać do {limit} liter, cyfr i podkreślników."),
+    ("alias_missing_command", "podaj polecenie do uruchomienia."),
+    ("note_limit", "czat nie może mieć więcej niż {limit} notatek."),
+    ("note", "notatka "),
+    ("note_saved", " została zapisana."),
+    ("note_replaced", " została zastąpiona."),
+    ("note_removed", " została usunięta."),
+    ("no_such_note", "na tym czacie nie ma takiej notatki."),
+    ("no_notes", "ten czat nie ma notatek. zapisz jedną poleceniem /save <nazwa>."),
+    ("note_list", "notatki na tym czacie:"),
+    ("note_list_hint", "\n\npokaż jedną poleceniem "),
+    ("note_name_too_long", "nazwy notatek nie mogą być dłuższe niż {limit} znaków."),
+    ("note_missing_text", "napisz treść notatki lub odpowiedz na wiadomość."),
+    ("note_unsupported_message", "tej wiadomości nie można zapisać jako notatki."),
+];
+
+const POLISH_COMMANDS: &[(&str, &str)] = &[
+    ("help", "lista poleceń lub instrukcja użycia jednego z nich"),
+    ("config", "konfiguracja ustawień bota"),
+    ("markov_chain", "generowanie tekstu na podstawie wiadomości z czatu"),
+    ("translate", "tłumaczenie tekstu przez Tłumacza Google"),
+    ("badtranslate", "złe tłumaczenie tekstu, słowo po słowie"),
+    ("trollslate", "tłumaczenie tekstu przez wiele losowych języków"),
+    ("convert", "przeliczanie popularnych walut i kryptowalut"),
+    ("urbandictionary", "definicja słowa z Urban Dictionary"),
+    ("screenshot", "zrzut ekranu strony internetowej"),
+    ("charinfo", "nazwy znaków Unicode"),
+    ("polymarket", "zakłady Polymarket dotyczące wydarzeń na świecie"),
+    ("autocomplete", "autouzupełnianie zapytania przez Google"),
+    ("petittube", "losowy film na YouTube z prawie zerową liczbą wyświetleń"),
+    ("does_kiwifarms_work", "sprawdzenie, czy forum The Kiwi Farms nadal działa"),
+    ("kebab", "generowanie typowej nazwy budki z kebabem"),
+    ("ping", "sprawdzenie, czy bot działa"),
+    ("cancel", "anulowanie twoich uruchomionych poleceń"),
+    ("alias", "lista lub definicja własnych poleceń na tym czacie"),
+    ("unalias", "usunięcie własnego polecenia z tego czatu"),
+    ("save", "zapisanie notatki na tym czacie"),
+    ("get", "wyświetlenie notatki zapisanej na tym czacie"),
+    ("notes", "lista notatek zapisanych na tym czacie"),
+    ("clear", "usunięcie notatki z tego czatu"),
+];
+
+/// the description of a command in the language, if it's translated
+pub fn command_description(language: Language, command: &str) -> Option<&'static str> {
+    language.command_catalog().iter().find(|(name, _)| *name == command).map(|(_, text)| *text)
+}
+
+/// the language of the chat if it's set in the config, or the language of the
+/// user
+pub fn language(bot_state: &BotState, chat_id: Option<i64>, user: &CompactUser) -> Language {
+    let chat_language = chat_id
+        .and_then(|chat_id| bot_state.config.lock().unwrap().chat_languages.get(&chat_id).cloned());
+
+    chat_language
+        .as_deref()
+        .and_then(Language::from_code)
+        .or_else(|| Language::from_code(&user.language_code))
+        .unwrap_or(Language::English)
+}
+
+/// looks up a message, falling back to English
+pub fn text(language: Language, key: &str) -> &'static str {
+    [language, Language::English]
+        .into_iter()
+        .find_map(|language| {
+            language.catalog().iter().find(|(name, _)| *name == key).map(|(_, text)| *text)
+        })
+        .unwrap_or_else(|| panic!("missing message {key:?}"))
+}
+
+/// looks up the plural form of a message for the count, and replaces `{count}`
+/// with it. English messages have the `_one` and `_many` forms, and Polish ones
+/// also have the `_few` form.
+pub fn format_plural(language: Language, key: &str, count: usize) -> String {
+    let form = match language {
+        _ if count == 1 => "one",
+        Language::English => "many",
+        Language::Polish => {
+            if (2..=4).contains(&(count % 10)) && !(12..=14).contains(&(count % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+    };
+
+    format(language, &format!("{key}_{form}"), &[("count", &count)])
+}
+
+/// looks up a message and replaces `{name}` placeholders with the arguments.
+/// placeholders in the arguments are left as they are.
+pub fn format(language: Language, key: &str, arguments: &[(&str, &dyn Display)]) -> String {
+    let mut text = text(language, key);
+    let mut formatted = String::with_capacity(text.len());
+
+    while let Some(start) = text.find('{') {
+        formatted.push_str(&text[..start]);
+        text = &text[start..];
+
+        let argument = text.find('}').and_then(|end| {
+            arguments.iter().find(|(name, _)| *name == &text[1..end]).map(|(_, value)| (end, value))
+        });
+
+        if let Some((end, value)) = argument {
+            write!(formatted, "{value}").unwrap();
+            text = &text[end + 1..];
+        } else {
+            formatted.push('{');
+            text = &text[1..];
+        }
+    }
+
+    formatted.push_str(text);
+    formatted
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_language_from_code() {
+        assert_eq!(Language::from_code("pl"), Some(Language::Polish));
+        assert_eq!(Language::from_code("en-US"), Some(Language::English));
+        assert_eq!(Language::from_code("pt-br"), None);
+        assert_eq!(Language::from_code(""), None);
+    }
+
+    #[test]
+    fn test_catalogs() {
+        for (key, _) in ENGLISH {
+            assert!(POLISH.iter().any(|(name, _)| name == key), "{key:?} missing in Polish");
+        }
+
+        assert_eq!(
+            format(Language::Polish, "queue_position", &[("position", &2)]),
+            "oczekiwanie w kolejce (pozycja 2)…"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            format(
+                Language::English,
+                "telegram_error",
+                &[("code", &"{message}"), ("message", &"}{")]
+            ),
+            "error {message}: }{"
+        );
+        assert_eq!(
+            format(Language::English, "bad_argument", &[]),
+            "bad command argument: {reason}"
+        );
+    }
+
+    /// every message used with a string literal key exists in every catalog,
+    /// and no other keys are used
+    #[test]
+    fn test_used_keys() {
+        let mut paths = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src")];
+
+        while let Some(path) = paths.pop() {
+            if path.is_dir() {
+                paths.extend(fs::read_dir(path).unwrap().map(|entry| entry.unwrap().path()));
+                continue;
+            }
+
+            if path.extension().is_none_or(|extension| extension != "rs")
+                || path.ends_with("utilities/i18n.rs")
+            {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+
+            for (function, forms) in [
+                ("i18n::text(", &[""][..]),
+                ("i18n::format(", &[""]),
+                ("i18n::format_plural(", &["_one", "_few", "_many"]),
+            ] {
+                for (index, _) in source.match_indices(function) {
+                    let key =
+                        key_argument(&source[index + function.len()..]).unwrap_or_else(|| {
+                            panic!(
+                                "{function} in {} isn't called with a literal key",
+                                path.display()
+                            )
+                        });
+
+                    for language in Language::ALL {
+                        let forms = forms
+                            .iter()
+                            .filter(|form| language != Language::English || **form != "_few");
+
+                        for form in forms {
+                            let key = format!("{key}{form}");
+                            assert!(
+                                language.catalog().iter().any(|(name, _)| *name == key),
+                                "{key:?} used in {} missing in {language:?}",
+                                path.display()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// the string literal passed as the second argument
+    fn key_argument(arguments: &str) -> Option<&str> {
+        let mut depth = 0;
+
+        let (comma, _) = arguments.char_indices().find(|&(_, char)| {
+            match char {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => (),
+            }
+
+            char == ',' && depth == 0
+        })?;
+
+        arguments[comma + 1..].trim_start().strip_prefix('"')?.split('"').next()
+    }
+
+    #[test]
+    fn test_format_plural() {
+        assert_eq!(
+            format_plural(Language::English, "cancelled_commands", 1),
+            "cancelled 1 command."
+        );
+        assert_eq!(
+            format_plural(Language::English, "cancelled_commands", 2),
+            "cancelled 2 commands."
+        );
+        assert_eq!(
+            format_plural(Language::Polish, "cancelled_commands", 22),
+            "anulowano 22 polecenia."
+        );
+        assert_eq!(
+            format_plural(Language::Polish, "cancelled_commands", 12),
+            "anulowano 12 poleceń."
+        );
+    }
+}
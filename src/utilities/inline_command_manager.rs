@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::commands::InlineCommandTrait;
+
+pub type InlineCommandRef = Arc<dyn InlineCommandTrait + Send + Sync>;
+
+pub struct InlineCommandManager {
+    commands: Vec<InlineCommandRef>,
+}
+
+impl InlineCommandManager {
+    pub const fn new() -> Self {
+        Self { commands: Vec::new() }
+    }
+
+    pub fn add_command(&mut self, command: impl InlineCommandTrait + Send + Sync + 'static) {
+        self.commands.push(Arc::new(command));
+    }
+
+    /// the command and its arguments. queries without arguments aren't matched,
+    /// so they're left to the calculator, which e.g. a bare `c` query is
+    /// more likely meant for.
+    pub fn get_command(&self, query: &str) -> Option<(InlineCommandRef, String)> {
+        let query = query.trim_ascii_start();
+        let (prefix, arguments) = query.split_once(|char: char| char.is_ascii_whitespace())?;
+
+        if arguments.trim_ascii().is_empty() {
+            return None;
+        }
+
+        let prefix = prefix.to_ascii_lowercase();
+
+        let command =
+            self.commands.iter().find(|c| c.inline_prefixes().contains(&prefix.as_str()))?;
+
+        Some((command.clone(), arguments.into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::charinfo::CharInfo;
+
+    #[test]
+    fn test_get_command() {
+        let mut inline_command_manager = InlineCommandManager::new();
+        inline_command_manager.add_command(CharInfo);
+
+        assert!(inline_command_manager.get_command("ch").is_none());
+        assert!(inline_command_manager.get_command("ch  ").is_none());
+        assert!(inline_command_manager.get_command("2 + 2").is_none());
+
+        let (_, arguments) = inline_command_manager.get_command(" CH abc").unwrap();
+        assert_eq!(arguments, "abc");
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use tdlib::enums::{InputInlineQueryResult, InputMessageContent};
+use tdlib::types::{
+    FormattedText, InputInlineQueryResultArticle, InputMessageText, LinkPreviewOptions,
+};
+
+use super::bot_state::BotState;
+use super::cache::CompactUser;
+use super::i18n::{self, Language};
+use super::telegram_client::TelegramClient;
+use crate::bot::TdResult;
+
+pub struct InlineArticle {
+    pub title: String,
+    pub description: String,
+    pub text: FormattedText,
+}
+
+pub struct InlineQueryContext {
+    pub client: Arc<dyn TelegramClient>,
+    pub query_id: i64,
+    pub user: CompactUser,
+    pub bot_state: Arc<BotState>,
+}
+
+impl InlineQueryContext {
+    /// inline queries aren't tied to a chat, so this is always the language of
+    /// the user
+    pub fn language(&self) -> Language {
+        i18n::language(&self.bot_state, None, &self.user)
+    }
+
+    pub async fn answer_article(&self, article: InlineArticle, cache_time: i32) -> TdResult<()> {
+        self.client
+            .answer_inline_query(
+                self.query_id,
+                vec![InputInlineQueryResult::Article(InputInlineQueryResultArticle {
+                    id: "0".into(),
+                    url: String::new(),
+                    title: article.title,
+                    description: article.description,
+                    thumbnail_url: String::new(),
+                    thumbnail_width: 0,
+                    thumbnail_height: 0,
+                    reply_markup: None,
+                    input_message_content: InputMessageContent::InputMessageText(
+                        InputMessageText {
+                            text: article.text,
+                            link_preview_options: Some(LinkPreviewOptions {
+                                is_disabled: true,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    ),
+                })],
+                cache_time,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_answer_article() {
+        let (command_ctx, client) = test_fixtures::fake_command_context();
+        let ctx = InlineQueryContext {
+            client: client.clone(),
+            query_id: 0,
+            user: command_ctx.user,
+            bot_state: command_ctx.bot_state,
+        };
+
+        let article = InlineArticle {
+            title: "title".into(),
+            description: String::new(),
+            text: FormattedText { text: "text".into(), ..Default::default() },
+        };
+
+        ctx.answer_article(article, 0).await.unwrap();
+
+        let answers = client.inline_answers.lock().unwrap();
+        let [InputInlineQueryResult::Article(result)] = answers.as_slice() else {
+            panic!("expected a single article");
+        };
+        assert_eq!(result.title, "title");
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use tokio::sync::watch;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ConcurrencyClass {
+    HeavyMedia,
+    ImageGeneration,
+}
+
+impl ConcurrencyClass {
+    const fn limit(self) -> usize {
+        match self {
+            Self::HeavyMedia => 2,
+            Self::ImageGeneration => 3,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ClassQueue {
+    running: usize,
+    next_id: u64,
+    waiting: VecDeque<(u64, watch::Sender<usize>)>,
+}
+
+#[derive(Default)]
+pub struct JobQueue {
+    queues: Mutex<HashMap<ConcurrencyClass, ClassQueue>>,
+}
+
+impl JobQueue {
+    pub fn enqueue(&self, class: ConcurrencyClass) -> Job<'_> {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.entry(class).or_default();
+
+        let id = queue.next_id;
+        queue.next_id += 1;
+
+        let position = if queue.running < class.limit() {
+            queue.running += 1;
+            0
+        } else {
+            queue.waiting.len() + 1
+        };
+
+        let (sender, receiver) = watch::channel(position);
+
+        if position > 0 {
+            queue.waiting.push_back((id, sender));
+        }
+
+        Job { queue: self, class, id, position: receiver }
+    }
+
+    fn finish(&self, class: ConcurrencyClass, id: u64, position: &watch::Receiver<usize>) {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.get_mut(&class).unwrap();
+
+        // the position can only change while the lock is held
+        if *position.borrow() == 0 {
+            queue.running -= 1;
+        } else {
+            queue.waiting.retain(|(job_id, _)| *job_id != id);
+        }
+
+        while queue.running < class.limit() {
+            let Some((_, sender)) = queue.waiting.pop_front() else {
+                break;
+            };
+
+            sender.send_replace(0);
+            queue.running += 1;
+        }
+
+        for (i, (_, sender)) in queue.waiting.iter().enumerate() {
+            sender.send_if_modified(|position| {
+                let changed = *position != i + 1;
+                *position = i + 1;
+                changed
+            });
+        }
+    }
+}
+
+/// a slot in the job queue, freed when dropped
+pub struct Job<'a> {
+    queue: &'a JobQueue,
+    class: ConcurrencyClass,
+    id: u64,
+    position: watch::Receiver<usize>,
+}
+
+impl Job<'_> {
+    /// position in the queue, 0 if the job can already run
+    pub fn position(&self) -> usize {
+        *self.position.borrow()
+    }
+
+    pub async fn wait(&mut self) -> usize {
+        // the sender is only dropped after the job was allowed to run
+        self.position.changed().await.ok();
+        *self.position.borrow_and_update()
+    }
+}
+
+impl Drop for Job<'_> {
+    fn drop(&mut self) {
+        self.queue.finish(self.class, self.id, &self.position);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_queue() {
+        let queue = JobQueue::default();
+
+        let first = queue.enqueue(ConcurrencyClass::HeavyMedia);
+        let second = queue.enqueue(ConcurrencyClass::HeavyMedia);
+        let mut third = queue.enqueue(ConcurrencyClass::HeavyMedia);
+        let fourth = queue.enqueue(ConcurrencyClass::HeavyMedia);
+        let mut fifth = queue.enqueue(ConcurrencyClass::HeavyMedia);
+
+        assert_eq!(first.position(), 0);
+        assert_eq!(second.position(), 0);
+        assert_eq!(third.position(), 1);
+        assert_eq!(fourth.position(), 2);
+        assert_eq!(fifth.position(), 3);
+        assert_eq!(queue.enqueue(ConcurrencyClass::ImageGeneration).position(), 0);
+
+        drop(fourth);
+        assert_eq!(fifth.wait().await, 2);
+
+        drop(first);
+        assert_eq!(third.wait().await, 0);
+        assert_eq!(fifth.wait().await, 1);
+
+        drop(second);
+        assert_eq!(fifth.wait().await, 0);
+    }
+}
@@ -2,22 +2,147 @@
 //! joe: call it logchamp
 //! joe: please
 
-use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
 use std::sync::Mutex;
 
 use colored::{Color, Colorize};
+use log::kv::{self, VisitSource};
 use log::{Level, LevelFilter, Log, Metadata, Record};
-use time::macros;
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime, macros};
+
+const FILENAME: &str = ".log";
+const MEBIBYTE: u64 = 1024 * 1024;
+const MAX_ROTATED_FILES: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy)]
+enum Rotation {
+    Never,
+    Daily,
+    Size(u64),
+}
+
+struct LogFile {
+    writer: BufWriter<File>,
+    size: u64,
+    opened_on: Date,
+}
+
+impl LogFile {
+    fn open() -> io::Result<Self> {
+        let file = File::options().append(true).create(true).open(FILENAME)?;
+        let metadata = file.metadata()?;
+
+        // a file left by a previous run belongs to the day it was last written to,
+        // so a restart doesn't postpone its daily rotation
+        let opened_on = metadata
+            .modified()
+            .map_or_else(|_| OffsetDateTime::now_utc(), OffsetDateTime::from)
+            .date();
+
+        Ok(Self { writer: BufWriter::new(file), size: metadata.len(), opened_on })
+    }
+
+    fn should_rotate(&self, rotation: Rotation, now: OffsetDateTime) -> bool {
+        match rotation {
+            Rotation::Never => false,
+            Rotation::Daily => now.date() != self.opened_on,
+            Rotation::Size(max_size) => self.size >= max_size,
+        }
+    }
+
+    fn rotate(&mut self, now: OffsetDateTime) -> io::Result<()> {
+        self.writer.flush()?;
+
+        let suffix = now
+            .format(macros::format_description!("[year]-[month]-[day]T[hour]-[minute]-[second]"))
+            .unwrap();
+
+        fs::rename(FILENAME, format!("{FILENAME}.{suffix}"))?;
+        *self = Self::open()?;
+
+        let mut rotated = fs::read_dir(".")?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name.starts_with(&format!("{FILENAME}.")))
+            .collect::<Vec<_>>();
+
+        // the suffixes sort chronologically
+        rotated.sort_unstable();
+
+        for name in rotated.iter().rev().skip(MAX_ROTATED_FILES) {
+            fs::remove_file(name)?;
+        }
+
+        Ok(())
+    }
+}
 
 struct Logger {
-    file: Mutex<BufWriter<File>>,
+    format: Format,
+    rotation: Rotation,
+    file: Mutex<LogFile>,
 }
 
 impl Logger {
-    fn new(filename: &str) -> Self {
-        log::set_max_level(LevelFilter::Debug);
-        Self { file: Mutex::new(BufWriter::new(File::create(filename).unwrap())) }
+    /// invalid settings fall back to the defaults, and are reported in
+    /// `warnings`, because nothing can be logged before the logger is set
+    fn new(warnings: &mut Vec<String>) -> Self {
+        let max_level = match env::var("LOG_LEVEL") {
+            Ok(level) => level.parse().unwrap_or_else(|_| {
+                warnings.push(format!("invalid LOG_LEVEL {level:?}, logging at the debug level"));
+                LevelFilter::Debug
+            }),
+            Err(_) => LevelFilter::Debug,
+        };
+
+        let format = match env::var("LOG_FORMAT").as_deref() {
+            Ok("json") => Format::Json,
+            Ok("text") | Err(_) => Format::Text,
+            Ok(format) => {
+                warnings.push(format!("invalid LOG_FORMAT {format:?}, logging as text"));
+                Format::Text
+            }
+        };
+
+        // either "daily" or the maximum size in MiB
+        let rotation = match env::var("LOG_ROTATION").as_deref() {
+            Ok("daily") => Rotation::Daily,
+            Ok(size) => match size.parse::<u64>() {
+                Ok(size) if size > 0 => Rotation::Size(size * MEBIBYTE),
+                _ => {
+                    warnings.push(format!(
+                        "invalid LOG_ROTATION {size:?}, {FILENAME} will not be rotated"
+                    ));
+                    Rotation::Never
+                }
+            },
+            Err(_) => Rotation::Never,
+        };
+
+        log::set_max_level(max_level);
+        Self { format, rotation, file: Mutex::new(LogFile::open().unwrap()) }
+    }
+
+    fn write_file(&self, line: &str, now: OffsetDateTime) {
+        let mut file = self.file.lock().unwrap();
+
+        if file.should_rotate(self.rotation, now)
+            && let Err(err) = file.rotate(now)
+        {
+            eprintln!("failed to rotate {FILENAME}: {err}");
+        }
+
+        writeln!(file.writer, "{line}").unwrap();
+        file.size += u64::try_from(line.len()).unwrap() + 1;
     }
 }
 
@@ -34,13 +159,30 @@ impl Log for Logger {
             return;
         }
 
-        let timestamp = time::OffsetDateTime::now_utc()
-            .format(macros::format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"))
-            .unwrap();
+        let now = OffsetDateTime::now_utc();
         let target = record.target();
         let level = record.level().as_str();
         let args = record.args();
 
+        if self.format == Format::Json {
+            let mut fields = Map::new();
+            fields.insert("timestamp".into(), now.format(&Rfc3339).unwrap().into());
+            fields.insert("level".into(), level.into());
+            fields.insert("target".into(), target.into());
+            fields.insert("message".into(), args.to_string().into());
+            record.key_values().visit(&mut JsonFields(&mut fields)).unwrap();
+
+            let line = Value::Object(fields).to_string();
+            println!("{line}");
+            self.write_file(&line, now);
+
+            return;
+        }
+
+        let timestamp = now
+            .format(macros::format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"))
+            .unwrap();
+
         let color = match record.level() {
             Level::Error => Color::BrightRed,
             Level::Warn => Color::BrightYellow,
@@ -50,14 +192,30 @@ impl Log for Logger {
         };
 
         println!("{} {} {args}", timestamp.color(Color::BrightBlack), level.color(color));
-        writeln!(self.file.lock().unwrap(), "{timestamp} [{target} {level}] {args}").unwrap();
+        self.write_file(&format!("{timestamp} [{target} {level}] {args}"), now);
     }
 
     fn flush(&self) {
-        self.file.lock().unwrap().flush().unwrap();
+        self.file.lock().unwrap().writer.flush().unwrap();
+    }
+}
+
+struct JsonFields<'a>(&'a mut Map<String, Value>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields<'_> {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        let value = serde_json::to_value(value).map_err(kv::Error::boxed)?;
+        self.0.insert(key.as_str().into(), value);
+
+        Ok(())
     }
 }
 
 pub fn init() {
-    log::set_boxed_logger(Box::new(Logger::new(".log"))).unwrap();
+    let mut warnings = Vec::new();
+    log::set_boxed_logger(Box::new(Logger::new(&mut warnings))).unwrap();
+
+    for warning in warnings {
+        log::warn!("{warning}");
+    }
 }
@@ -1,30 +1,22 @@
-use std::fs::File;
-use std::io::{self, BufReader, BufWriter};
-use std::path::Path;
-
 use markov_chain::MarkovChain;
 
-const FILENAME: &str = "markov_chain.dat";
+use super::persistence::Persistent;
+
 const MARKOV_CHAIN_ORDER: usize = 3;
 
-pub fn load() -> io::Result<MarkovChain> {
-    let path = Path::new(FILENAME);
+impl Persistent for MarkovChain {
+    const FILENAME: Option<&'static str> = Some("markov_chain.dat");
+    const KEY: &'static str = "markov_chain";
+    const NAME: &'static str = "Markov chain";
+    const VERSION: u32 = 1;
 
-    if path.exists() {
-        log::debug!("loading Markov chain from drive");
-        Ok(rmp_serde::decode::from_read(BufReader::new(File::open(path)?)).unwrap())
-    } else {
-        log::debug!("creating a new Markov chain");
-        Ok(MarkovChain::new(MARKOV_CHAIN_ORDER))
+    fn create() -> Self {
+        Self::new(MARKOV_CHAIN_ORDER)
     }
-}
 
-pub fn save(markov_chain: &MarkovChain) -> io::Result<()> {
-    log::debug!("saving Markov chain to drive");
-    let file = File::options().write(true).truncate(true).create(true).open(FILENAME)?;
-    rmp_serde::encode::write(&mut BufWriter::new(file), markov_chain).unwrap();
-
-    Ok(())
+    fn encode(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
 }
 
 pub fn train(markov_chain: &mut MarkovChain, mut text: String) {
@@ -7,13 +7,23 @@ use super::bot_state::BotState;
 use super::command_context::CommandContext;
 use super::command_manager::CommandInstance;
 use super::parsed_command::ParsedCommand;
+use super::telegram_client::TdlibClient;
 use super::telegram_utils;
 use crate::bot::Bot;
 
 pub enum MessageDestination {
-    Command { command: Arc<CommandInstance>, arguments: String, context: Box<CommandContext> },
-    Dice { message: Box<Message> },
-    MarkovChain { text: String },
+    Command {
+        command: Arc<CommandInstance>,
+        arguments: String,
+        context: Box<CommandContext>,
+        admin_only: bool,
+    },
+    Dice {
+        message: Box<Message>,
+    },
+    MarkovChain {
+        text: String,
+    },
 }
 
 pub fn message_destination(
@@ -29,6 +39,10 @@ pub fn message_destination(
         return None; // ignore messages not sent by users
     };
 
+    if bot_state.config.lock().unwrap().blocked_users.contains(&user_id) {
+        return None; // ignore blocked users
+    }
+
     let Some(user) = bot_state.cache.lock().unwrap().get_user(user_id) else {
         log::warn!("user {user_id} not found in cache");
         return None; // ignore users not in cache
@@ -65,15 +79,38 @@ pub fn message_destination(
             }
         }
 
-        let Some(command) = bot.get_command(&parsed_command.name) else {
-            return None; // ignore nonexistent commands
+        let (command, arguments) = if let Some(command) = bot.get_command(&parsed_command.name) {
+            (command, parsed_command.arguments)
+        } else {
+            let alias = bot_state
+                .config
+                .lock()
+                .unwrap()
+                .command_alias(message.chat_id, &parsed_command.name)
+                .cloned()?; // ignore nonexistent commands
+
+            let command = bot.get_command(&alias.command)?; // ignore aliases of removed commands
+
+            (command, alias.expand(&parsed_command.arguments))
+        };
+
+        let admin_only = {
+            let config = bot_state.config.lock().unwrap();
+
+            if config.is_command_disabled(message.chat_id, command.name()) {
+                return None; // ignore commands disabled in this chat
+            }
+
+            config.is_command_admin_only(message.chat_id, command.name())
         };
 
         Some(MessageDestination::Command {
             command,
-            arguments: parsed_command.arguments,
+            arguments,
+            admin_only,
             context: Box::new(CommandContext {
                 client_id: bot.client_id,
+                client: Arc::new(TdlibClient::new(bot.client_id)),
                 chat,
                 user,
                 message,
@@ -1,13 +1,22 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use tdlib::types::{Message, UpdateMessageSendFailed, UpdateMessageSendSucceeded};
 
 use crate::bot::{TdError, TdResult};
 
+/// how long results nobody waited for yet are kept
+const UNCLAIMED_TTL: Duration = Duration::from_mins(1);
+
 #[derive(Default)]
 pub struct MessageQueue {
     queue: Mutex<HashMap<i64, oneshot::Sender<TdResult<Message>>>>,
+    edited: Mutex<HashMap<i64, (Message, Instant)>>,
+    /// results which arrived before anyone waited for them, as TDLib can report
+    /// a message as sent before the request which sent it returns to the
+    /// command
+    sent: Mutex<HashMap<i64, (TdResult<Message>, Instant)>>,
 }
 
 impl MessageQueue {
@@ -18,11 +27,22 @@ impl MessageQueue {
     pub async fn wait_for_messages(&self, message_ids: &[i64]) -> Vec<TdResult<Message>> {
         let receivers = {
             let mut queue = self.queue.lock().unwrap();
+            let mut edited = self.edited.lock().unwrap();
+            let mut sent = self.sent.lock().unwrap();
+            prune(&mut edited);
+            prune(&mut sent);
             message_ids
                 .iter()
                 .map(|&message_id| {
                     let (tx, rx) = oneshot::channel();
-                    queue.insert(message_id, tx);
+                    // edited messages are never reported as sent
+                    if let Some((message, _)) = edited.remove(&message_id) {
+                        tx.send(Ok(message)).unwrap();
+                    } else if let Some((result, _)) = sent.remove(&message_id) {
+                        tx.send(result).unwrap();
+                    } else {
+                        queue.insert(message_id, tx);
+                    }
                     rx
                 })
                 .collect::<Vec<_>>()
@@ -48,10 +68,44 @@ impl MessageQueue {
             ),
         };
 
-        let tx = self.queue.lock().unwrap().remove(&old_message_id);
+        let mut queue = self.queue.lock().unwrap();
 
-        if let Some(tx) = tx {
+        if let Some(tx) = queue.remove(&old_message_id) {
             tx.send(result).unwrap();
+        } else {
+            let mut sent = self.sent.lock().unwrap();
+            prune(&mut sent);
+            sent.insert(old_message_id, (result, Instant::now()));
         }
     }
+
+    pub fn message_edited(&self, message: Message) {
+        let mut edited = self.edited.lock().unwrap();
+        prune(&mut edited);
+        edited.insert(message.id, (message, Instant::now()));
+    }
+}
+
+fn prune<T>(results: &mut HashMap<i64, (T, Instant)>) {
+    results.retain(|_, (_, received_at)| received_at.elapsed() < UNCLAIMED_TTL);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_sent_before_waiting() {
+        let message_queue = MessageQueue::default();
+
+        message_queue.message_sent(Ok(UpdateMessageSendSucceeded {
+            message: test_fixtures::text_message(1, 2, "text"),
+            old_message_id: 1,
+        }));
+
+        let message = message_queue.wait_for_message(1).await.unwrap();
+        assert_eq!(message.id, 2);
+        assert!(message_queue.sent.lock().unwrap().is_empty());
+    }
 }
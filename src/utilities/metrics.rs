@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use tdlib::enums::ConnectionState;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::bot_state::{BotState, BotStatus};
+use super::endpoints::Endpoint;
+
+const CONNECTION_STATES: [(ConnectionState, &str); 5] = [
+    (ConnectionState::WaitingForNetwork, "waiting_for_network"),
+    (ConnectionState::ConnectingToProxy, "connecting_to_proxy"),
+    (ConnectionState::Connecting, "connecting"),
+    (ConnectionState::Updating, "updating"),
+    (ConnectionState::Ready, "ready"),
+];
+
+// failed requests per endpoint, counted by the retry layer, which is shared by
+// all commands and doesn't have access to the bot state
+static API_ERRORS: LazyLock<Mutex<HashMap<Endpoint, u64>>> = LazyLock::new(Mutex::default);
+
+/// runtime state which isn't tracked anywhere else, exposed by the metrics
+/// server
+#[derive(Default)]
+pub struct Metrics {
+    connection_state: Mutex<Option<ConnectionState>>,
+    tasks: AtomicUsize,
+}
+
+impl Metrics {
+    pub fn set_connection_state(&self, state: ConnectionState) {
+        *self.connection_state.lock().unwrap() = Some(state);
+    }
+
+    pub fn set_task_count(&self, count: usize) {
+        self.tasks.store(count, Ordering::Relaxed);
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        *self.connection_state.lock().unwrap() == Some(ConnectionState::Ready)
+    }
+}
+
+/// counts a request which failed with a network or server error, or wasn't sent
+/// because the circuit of the endpoint is open
+pub fn record_api_error(endpoint: Endpoint) {
+    *API_ERRORS.lock().unwrap().entry(endpoint).or_default() += 1;
+}
+
+pub async fn serve(address: String, bot_state: Arc<BotState>) {
+    let listener = match TcpListener::bind(&address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("failed to start the metrics server on {address}: {err}");
+            return;
+        }
+    };
+
+    log::info!("serving metrics on {address}");
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, bot_state.clone()));
+            }
+            Err(err) => log::warn!("failed to accept a metrics connection: {err}"),
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, bot_state: Arc<BotState>) {
+    let mut buffer = [0; 1024];
+
+    let Ok(Ok(length)) =
+        tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buffer)).await
+    else {
+        return;
+    };
+
+    // only the path of the request line matters, e.g. "GET /metrics HTTP/1.1"
+    let request = String::from_utf8_lossy(&buffer[..length]);
+    let path = request.split_ascii_whitespace().nth(1).unwrap_or_default();
+
+    let (status, body) = match path {
+        "/metrics" => ("200 OK", render(&bot_state)),
+        "/healthz" => {
+            if bot_state.metrics.is_healthy() {
+                ("200 OK", "ok\n".into())
+            } else {
+                ("503 Service Unavailable", "TDLib is not connected\n".into())
+            }
+        }
+        _ => ("404 Not Found", "not found\n".into()),
+    };
+
+    let response = format!(
+        concat!(
+            "HTTP/1.1 {}\r\n",
+            "Content-Type: text/plain; version=0.0.4\r\n",
+            "Content-Length: {}\r\n",
+            "Connection: close\r\n\r\n",
+            "{}"
+        ),
+        status,
+        body.len(),
+        body
+    );
+
+    stream.write_all(response.as_bytes()).await.ok();
+}
+
+fn render(bot_state: &BotState) -> String {
+    let mut text = String::new();
+
+    let connection_state = bot_state.metrics.connection_state.lock().unwrap().clone();
+    text.push_str("# TYPE craiyon_bot_connection_state gauge\n");
+    for (state, name) in CONNECTION_STATES {
+        let value = u8::from(connection_state.as_ref() == Some(&state));
+        writeln!(text, "craiyon_bot_connection_state{{state=\"{name}\"}} {value}").unwrap();
+    }
+
+    let status = *bot_state.status.lock().unwrap();
+    text.push_str("# TYPE craiyon_bot_status gauge\n");
+    for (name, value) in [
+        ("running", matches!(status, BotStatus::Running)),
+        ("waiting_to_close", matches!(status, BotStatus::WaitingToClose)),
+        ("closing", matches!(status, BotStatus::Closing)),
+        ("closed", matches!(status, BotStatus::Closed)),
+    ] {
+        writeln!(text, "craiyon_bot_status{{status=\"{name}\"}} {}", u8::from(value)).unwrap();
+    }
+
+    text.push_str("# TYPE craiyon_bot_tasks gauge\n");
+    writeln!(text, "craiyon_bot_tasks {}", bot_state.metrics.tasks.load(Ordering::Relaxed))
+        .unwrap();
+
+    let stats = bot_state.stats.lock().unwrap();
+    let mut commands = stats.all_time().iter().collect::<Vec<_>>();
+    commands.sort_unstable_by_key(|(command, _)| *command);
+
+    text.push_str("# TYPE craiyon_bot_command_invocations_total counter\n");
+    for (command, stats) in &commands {
+        writeln!(
+            text,
+            "craiyon_bot_command_invocations_total{{command=\"{command}\"}} {}",
+            stats.invocations
+        )
+        .unwrap();
+    }
+
+    text.push_str("# TYPE craiyon_bot_command_errors_total counter\n");
+    for (command, stats) in &commands {
+        for (kind, count) in &stats.errors {
+            writeln!(
+                text,
+                "craiyon_bot_command_errors_total{{command=\"{command}\",kind=\"{kind}\"}} {count}"
+            )
+            .unwrap();
+        }
+    }
+
+    let api_errors = API_ERRORS.lock().unwrap();
+    text.push_str("# TYPE craiyon_bot_api_errors_total counter\n");
+    for endpoint in Endpoint::ALL {
+        let count = api_errors.get(&endpoint).copied().unwrap_or_default();
+
+        writeln!(text, "craiyon_bot_api_errors_total{{endpoint=\"{}\"}} {count}", endpoint.name())
+            .unwrap();
+    }
+
+    text
+}
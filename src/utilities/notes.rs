@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use tdlib::types::FormattedText;
+
+use super::persistence::Persistent;
+use super::telegram_utils::MessageAttachment;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum AttachmentKind {
+    Animation,
+    Audio,
+    Document,
+    Photo,
+    Sticker,
+    Video,
+    VideoNote,
+    VoiceNote,
+}
+
+/// a file saved by its remote ID, which can be sent again without downloading
+/// it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NoteAttachment {
+    pub kind: AttachmentKind,
+    pub file_id: String,
+}
+
+impl From<&MessageAttachment<'_>> for NoteAttachment {
+    fn from(value: &MessageAttachment) -> Self {
+        let kind = match value {
+            MessageAttachment::Animation(_) => AttachmentKind::Animation,
+            MessageAttachment::Audio(_) => AttachmentKind::Audio,
+            MessageAttachment::Document(_) => AttachmentKind::Document,
+            MessageAttachment::Photo(_) | MessageAttachment::ChatChangePhoto(_) => {
+                AttachmentKind::Photo
+            }
+            MessageAttachment::Sticker(_) => AttachmentKind::Sticker,
+            MessageAttachment::Video(_) => AttachmentKind::Video,
+            MessageAttachment::VideoNote(_) => AttachmentKind::VideoNote,
+            MessageAttachment::VoiceNote(_) => AttachmentKind::VoiceNote,
+        };
+
+        Self { kind, file_id: value.file().remote.id.clone() }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub text: FormattedText,
+    pub attachment: Option<NoteAttachment>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Notes {
+    chats: HashMap<i64, BTreeMap<String, Note>>,
+}
+
+impl Notes {
+    pub fn get(&self, chat_id: i64, name: &str) -> Option<&Note> {
+        self.chats.get(&chat_id)?.get(name)
+    }
+
+    pub fn names(&self, chat_id: i64) -> Vec<String> {
+        self.chats.get(&chat_id).map(|notes| notes.keys().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn count(&self, chat_id: i64) -> usize {
+        self.chats.get(&chat_id).map_or(0, BTreeMap::len)
+    }
+
+    /// returns whether a note with the name was replaced
+    pub fn insert(&mut self, chat_id: i64, name: String, note: Note) -> bool {
+        self.chats.entry(chat_id).or_default().insert(name, note).is_some()
+    }
+
+    pub fn remove(&mut self, chat_id: i64, name: &str) -> Option<Note> {
+        let notes = self.chats.get_mut(&chat_id)?;
+        let note = notes.remove(name);
+
+        if notes.is_empty() {
+            self.chats.remove(&chat_id);
+        }
+
+        note
+    }
+}
+
+impl Persistent for Notes {
+    const KEY: &'static str = "notes";
+    const NAME: &'static str = "notes";
+    const VERSION: u32 = 1;
+
+    fn create() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::{fmt, fs, io, mem};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::storage::{Storage, StorageError};
+
+// files written before the header was introduced don't start with it and are
+// version 0
+const MAGIC: &[u8; 4] = b"CRBT";
+
+pub enum LoadError {
+    Io(io::Error),
+    Decode(rmp_serde::decode::Error),
+    UnknownVersion(u32),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Decode(err) => write!(f, "decoding failed: {err}"),
+            Self::UnknownVersion(version) => write!(f, "unknown version {version}"),
+        }
+    }
+}
+
+impl From<rmp_serde::decode::Error> for LoadError {
+    fn from(value: rmp_serde::decode::Error) -> Self {
+        Self::Decode(value)
+    }
+}
+
+/// state saved as a versioned MessagePack blob
+pub trait Persistent: Serialize + DeserializeOwned {
+    const NAME: &'static str;
+    const KEY: &'static str;
+    /// file the state was saved to before it was moved to the storage
+    const FILENAME: Option<&'static str> = None;
+    const VERSION: u32;
+
+    fn create() -> Self;
+
+    /// decodes data saved by an older version of the bot. version 0 files only
+    /// lack the header of version 1, so they're decoded the same way.
+    fn migrate(version: u32, data: &[u8]) -> Result<Self, LoadError> {
+        if version == Self::VERSION || (version == 0 && Self::VERSION == 1) {
+            Ok(rmp_serde::from_slice(data)?)
+        } else {
+            Err(LoadError::UnknownVersion(version))
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+}
+
+/// loads the state, importing it from its old file if it's not in the storage
+/// yet, or creates a new one if it's missing or can't be decoded
+pub fn load<T: Persistent>(storage: &dyn Storage) -> Result<T, StorageError> {
+    let Some((version, data)) = storage.load(T::KEY)? else {
+        return import(storage);
+    };
+
+    log::debug!("loading {} from storage", T::NAME);
+
+    match T::migrate(version, &data) {
+        Ok(value) => Ok(value),
+        Err(err) => {
+            let backup = format!("{}.corrupt", T::KEY);
+            log::error!("failed to load {}: {err}, moving it to {backup}", T::NAME);
+            storage.save(&backup, version, &data)?;
+
+            Ok(T::create())
+        }
+    }
+}
+
+/// encodes the state while it's locked, and writes it after unlocking it
+pub fn save<T: Persistent>(storage: &dyn Storage, value: &Mutex<T>) -> Result<(), StorageError> {
+    let data = value.lock().unwrap().encode()?;
+    write::<T>(storage, &data)
+}
+
+fn write<T: Persistent>(storage: &dyn Storage, data: &[u8]) -> Result<(), StorageError> {
+    log::debug!("saving {} to storage", T::NAME);
+    storage.save(T::KEY, T::VERSION, data)
+}
+
+fn import<T: Persistent>(storage: &dyn Storage) -> Result<T, StorageError> {
+    let Some(filename) =
+        T::FILENAME.filter(|filename| storage.imports_files() && Path::new(filename).exists())
+    else {
+        log::debug!("creating a new {}", T::NAME);
+        return Ok(T::create());
+    };
+
+    let path = Path::new(filename);
+
+    log::info!("importing {} from {filename}", T::NAME);
+
+    let result = fs::read(path).map_err(LoadError::Io).and_then(|data| {
+        let (version, data) = match data.strip_prefix(MAGIC) {
+            Some(data) if data.len() >= mem::size_of::<u32>() => {
+                let (version, data) = data.split_at(mem::size_of::<u32>());
+                (u32::from_le_bytes(version.try_into().unwrap()), data)
+            }
+            _ => (0, data.as_slice()),
+        };
+
+        T::migrate(version, data)
+    });
+
+    let (value, renamed) = match result {
+        Ok(value) => {
+            write::<T>(storage, &value.encode()?)?;
+            (value, format!("{filename}.imported"))
+        }
+        Err(err) => {
+            log::error!("failed to import {}: {err}", T::NAME);
+            (T::create(), format!("{filename}.corrupt"))
+        }
+    };
+
+    if let Err(err) = fs::rename(path, &renamed) {
+        log::error!("failed to move {filename} to {renamed}: {err}");
+    }
+
+    Ok(value)
+}
@@ -1,42 +1,215 @@
 use std::collections::HashMap;
-use std::hash::Hash;
 
-pub struct RateLimiter<K> {
-    limit: usize,
-    duration: i32,
-    history: HashMap<K, Vec<i32>>,
+use serde::{Deserialize, Serialize};
+
+use super::persistence::Persistent;
+
+// how often full buckets, which behave the same as missing ones, are forgotten
+const EVICTION_INTERVAL: i64 = 10 * 60;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RateLimitScope {
+    /// each user of the command
+    User,
+    /// each chat the command is used in
+    Chat,
+    /// all uses of the command
+    Global,
+    /// all commands using the same external API
+    Api(&'static str),
 }
 
-impl<K: Eq + Hash> RateLimiter<K> {
-    pub fn new(limit: usize, duration: i32) -> Self {
-        Self { limit, duration, history: HashMap::new() }
+/// allows `capacity` uses at once, refilled at a rate of `capacity` uses per
+/// `period` seconds. unlike a window of `period` seconds, uses come back one by
+/// one, so e.g. `user(3, 300)` allows another use 100 seconds after the third
+/// one.
+#[derive(Clone, Copy)]
+pub struct RateLimit {
+    scope: RateLimitScope,
+    capacity: u32,
+    period: u32,
+}
+
+impl RateLimit {
+    const fn new(scope: RateLimitScope, capacity: u32, period: u32) -> Self {
+        assert!(capacity > 0 && period > 0, "rate limits need a positive capacity and period");
+        Self { scope, capacity, period }
     }
 
-    pub fn update_rate_limit(&mut self, key: K, time: i32) -> Option<i32> {
-        if let Some(value) = self.history.get_mut(&key) {
-            let cooldown = if value.len() >= self.limit {
-                value.truncate(self.limit);
-                let last_time = *value.last().unwrap();
-                if time - last_time < self.duration {
-                    Some(self.duration - (time - last_time))
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+    pub const fn user(capacity: u32, period: u32) -> Self {
+        Self::new(RateLimitScope::User, capacity, period)
+    }
+
+    pub const fn chat(capacity: u32, period: u32) -> Self {
+        Self::new(RateLimitScope::Chat, capacity, period)
+    }
+
+    pub const fn global(capacity: u32, period: u32) -> Self {
+        Self::new(RateLimitScope::Global, capacity, period)
+    }
+
+    pub const fn api(name: &'static str, capacity: u32, period: u32) -> Self {
+        Self::new(RateLimitScope::Api(name), capacity, period)
+    }
+
+    fn rate(self) -> f64 {
+        f64::from(self.capacity) / f64::from(self.period)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum BucketKey {
+    User(String, i64),
+    Chat(String, i64),
+    Global(String),
+    Api(String),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Bucket {
+    tokens: f64,
+    capacity: u32,
+    period: u32,
+    updated_at: i64,
+}
+
+impl Bucket {
+    fn tokens_at(&self, now: i64) -> f64 {
+        #[expect(clippy::cast_precision_loss)]
+        let elapsed = (now - self.updated_at).max(0) as f64;
+        let rate = f64::from(self.capacity) / f64::from(self.period);
+
+        elapsed.mul_add(rate, self.tokens).min(f64::from(self.capacity))
+    }
+
+    fn refill(&mut self, limit: RateLimit, now: i64) {
+        self.tokens = self.tokens_at(now);
+        self.capacity = limit.capacity;
+        self.period = limit.period;
+        self.updated_at = now;
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimiter {
+    buckets: HashMap<BucketKey, Bucket>,
+    evicted_at: i64,
+}
+
+impl RateLimiter {
+    /// takes a use from every limit, or returns the scope and cooldown in
+    /// seconds of the limit which will be exhausted for the longest time
+    pub fn check(
+        &mut self,
+        command: &str,
+        user_id: i64,
+        chat_id: i64,
+        limits: &[RateLimit],
+        now: i64,
+    ) -> Option<(RateLimitScope, u64)> {
+        if now - self.evicted_at >= EVICTION_INTERVAL {
+            self.buckets.retain(|_, bucket| bucket.tokens_at(now) < f64::from(bucket.capacity));
+            self.evicted_at = now;
+        }
+
+        let mut cooldown = None::<(RateLimitScope, u64)>;
 
-            if cooldown.is_none() {
-                value.insert(0, time);
+        for &limit in limits {
+            let bucket = self.bucket(command, user_id, chat_id, limit, now);
+
+            if bucket.tokens < 1. {
+                #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let seconds = ((1. - bucket.tokens) / limit.rate()).ceil() as u64;
+
+                if cooldown.is_none_or(|(_, longest)| seconds > longest) {
+                    cooldown = Some((limit.scope, seconds));
+                }
             }
-            cooldown
-        } else {
-            self.history.insert(key, vec![time]);
-            None
         }
+
+        if cooldown.is_some() {
+            return cooldown;
+        }
+
+        for &limit in limits {
+            self.bucket(command, user_id, chat_id, limit, now).tokens -= 1.;
+        }
+
+        None
+    }
+
+    fn bucket(
+        &mut self,
+        command: &str,
+        user_id: i64,
+        chat_id: i64,
+        limit: RateLimit,
+        now: i64,
+    ) -> &mut Bucket {
+        let key = match limit.scope {
+            RateLimitScope::User => BucketKey::User(command.into(), user_id),
+            RateLimitScope::Chat => BucketKey::Chat(command.into(), chat_id),
+            RateLimitScope::Global => BucketKey::Global(command.into()),
+            RateLimitScope::Api(name) => BucketKey::Api(name.into()),
+        };
+
+        let bucket = self.buckets.entry(key).or_insert_with(|| Bucket {
+            tokens: f64::from(limit.capacity),
+            capacity: limit.capacity,
+            period: limit.period,
+            updated_at: now,
+        });
+
+        bucket.refill(limit, now);
+
+        bucket
+    }
+}
+
+impl Persistent for RateLimiter {
+    const KEY: &'static str = "rate_limits";
+    const NAME: &'static str = "rate limits";
+    const VERSION: u32 = 1;
+
+    fn create() -> Self {
+        Self::default()
     }
 }
 
-pub struct RateLimits {
-    pub rate_limit_exceeded: RateLimiter<i64>,
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut rate_limiter = RateLimiter::default();
+        let limits = [RateLimit::user(2, 16), RateLimit::api("api", 3, 48)];
+
+        assert_eq!(rate_limiter.check("command", 1, 1, &limits, 0), None);
+        assert_eq!(rate_limiter.check("command", 1, 1, &limits, 0), None);
+        assert_eq!(
+            rate_limiter.check("command", 1, 1, &limits, 0),
+            Some((RateLimitScope::User, 8))
+        );
+        assert_eq!(
+            rate_limiter.check("command", 1, 1, &limits, 4),
+            Some((RateLimitScope::User, 4))
+        );
+        assert_eq!(rate_limiter.check("command", 2, 1, &limits, 4), None);
+        assert_eq!(
+            rate_limiter.check("other", 3, 1, &limits, 4),
+            Some((RateLimitScope::Api("api"), 12))
+        );
+        assert_eq!(rate_limiter.check("command", 1, 1, &limits, 16), None);
+
+        rate_limiter.check("command", 1, 1, &[], 16 + EVICTION_INTERVAL);
+        assert!(rate_limiter.buckets.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "positive capacity and period"]
+    fn test_zero_period() {
+        RateLimit::user(1, 0);
+    }
 }
@@ -0,0 +1,195 @@
+//! responses of lookup-style APIs, kept for a while so repeated queries don't
+//! hit the network
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::OnceCell;
+
+use super::endpoints::Endpoint;
+
+type Key = (TypeId, String);
+type Slot = Arc<OnceCell<CachedResponse>>;
+
+#[derive(Clone, Copy)]
+struct CachePolicy {
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl CachePolicy {
+    const fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self { ttl, max_entries }
+    }
+}
+
+/// responses of endpoints without a policy are never cached
+const fn cache_policy(endpoint: Endpoint) -> Option<CachePolicy> {
+    match endpoint {
+        Endpoint::Coinranking | Endpoint::Eurofxref => {
+            Some(CachePolicy::new(Duration::from_hours(1), 1))
+        }
+        Endpoint::Google => Some(CachePolicy::new(Duration::from_hours(1), 256)),
+        Endpoint::Mathjs => Some(CachePolicy::new(Duration::from_mins(10), 256)),
+        Endpoint::Polymarket => Some(CachePolicy::new(Duration::from_mins(5), 128)),
+        Endpoint::Translate => Some(CachePolicy::new(Duration::from_hours(1), 512)),
+        Endpoint::UrbanDictionary => Some(CachePolicy::new(Duration::from_hours(1), 256)),
+        _ => None,
+    }
+}
+
+struct CachedResponse {
+    fetched_at: Instant,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+#[derive(Default)]
+pub struct ResponseCache {
+    endpoints: Mutex<HashMap<Endpoint, HashMap<Key, Slot>>>,
+}
+
+impl ResponseCache {
+    /// returns the cached response to the request, or fetches it. concurrent
+    /// calls for the same request wait for a single fetch, and failed
+    /// fetches are not cached.
+    pub async fn get_or_fetch<T, E>(
+        &self,
+        endpoint: Endpoint,
+        request: impl Into<String>,
+        fetch: impl Future<Output = Result<T, E>>,
+    ) -> Result<Arc<T>, E>
+    where
+        T: Send + Sync + 'static,
+    {
+        let Some(policy) = cache_policy(endpoint) else {
+            return fetch.await.map(Arc::new);
+        };
+
+        let slot = self.slot(endpoint, (TypeId::of::<T>(), request.into()), policy, Instant::now());
+
+        let response = slot
+            .get_or_try_init(|| async {
+                fetch.await.map(|value| CachedResponse {
+                    fetched_at: Instant::now(),
+                    value: Arc::new(value),
+                })
+            })
+            .await?;
+
+        Ok(response.value.clone().downcast().unwrap())
+    }
+
+    fn slot(&self, endpoint: Endpoint, key: Key, policy: CachePolicy, now: Instant) -> Slot {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let slots = endpoints.entry(endpoint).or_default();
+
+        if let Some(slot) = slots.get(&key)
+            && !is_expired(slot, policy, now)
+        {
+            return slot.clone();
+        }
+
+        slots.remove(&key);
+
+        if slots.len() >= policy.max_entries {
+            evict(slots, policy, now);
+        }
+
+        slots.entry(key).or_default().clone()
+    }
+}
+
+fn is_expired(slot: &Slot, policy: CachePolicy, now: Instant) -> bool {
+    slot.get().is_some_and(|response| now.duration_since(response.fetched_at) >= policy.ttl)
+}
+
+/// removes expired responses and failed fetches nobody waits for, and then the
+/// oldest responses until there is space for a new one
+fn evict(slots: &mut HashMap<Key, Slot>, policy: CachePolicy, now: Instant) {
+    slots.retain(|_, slot| {
+        !is_expired(slot, policy, now) && (slot.initialized() || Arc::strong_count(slot) > 1)
+    });
+
+    while slots.len() >= policy.max_entries {
+        let Some(oldest) = slots
+            .iter()
+            .filter_map(|(key, slot)| Some((key, slot.get()?.fetched_at)))
+            .min_by_key(|(_, fetched_at)| *fetched_at)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+
+        slots.remove(&oldest);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    async fn fetch(count: &AtomicUsize, value: &'static str) -> Result<String, ()> {
+        count.fetch_add(1, Ordering::Relaxed);
+        Ok(value.into())
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch() {
+        let cache = ResponseCache::default();
+        let count = AtomicUsize::new(0);
+
+        let first = cache.get_or_fetch(Endpoint::Mathjs, "2+2", fetch(&count, "4")).await;
+        let second = cache.get_or_fetch(Endpoint::Mathjs, "2+2", fetch(&count, "5")).await;
+        assert_eq!(*first.unwrap(), "4");
+        assert_eq!(*second.unwrap(), "4");
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        let other = cache.get_or_fetch(Endpoint::Mathjs, "2+3", fetch(&count, "5")).await;
+        assert_eq!(*other.unwrap(), "5");
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+
+        // endpoints without a policy are always fetched
+        cache.get_or_fetch(Endpoint::Fal, "", fetch(&count, "")).await.unwrap();
+        cache.get_or_fetch(Endpoint::Fal, "", fetch(&count, "")).await.unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+
+        let failed =
+            cache.get_or_fetch::<String, _>(Endpoint::Mathjs, "x", async { Err(()) }).await;
+        assert!(failed.is_err());
+        let retried = cache.get_or_fetch(Endpoint::Mathjs, "x", fetch(&count, "x")).await;
+        assert_eq!(*retried.unwrap(), "x");
+    }
+
+    #[test]
+    fn test_expiry_and_eviction() {
+        let cache = ResponseCache::default();
+        let policy = CachePolicy::new(Duration::from_secs(60), 2);
+        let now = Instant::now();
+
+        for (i, request) in ["a", "b", "c"].into_iter().enumerate() {
+            let slot =
+                cache.slot(Endpoint::Mathjs, (TypeId::of::<()>(), request.into()), policy, now);
+            let fetched_at = now + Duration::from_secs(i as u64);
+            assert!(slot.set(CachedResponse { fetched_at, value: Arc::new(()) }).is_ok());
+        }
+
+        let endpoints = cache.endpoints.lock().unwrap();
+        let mut requests =
+            endpoints[&Endpoint::Mathjs].keys().map(|key| &*key.1).collect::<Vec<_>>();
+        requests.sort_unstable();
+        assert_eq!(requests, ["b", "c"]);
+        drop(endpoints);
+
+        let key = (TypeId::of::<()>(), "c".into());
+        let slot = cache.slot(Endpoint::Mathjs, key.clone(), policy, now);
+        assert!(slot.initialized());
+
+        let slot = cache.slot(Endpoint::Mathjs, key, policy, now + Duration::from_secs(62));
+        assert!(!slot.initialized());
+    }
+}
@@ -0,0 +1,284 @@
+//! retries of failed outbound requests, and circuit breakers which make
+//! requests fail fast while a service keeps failing
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use super::endpoints::Endpoint;
+use super::metrics;
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// requests asked to wait longer than this are not retried
+const MAX_DELAY: Duration = Duration::from_secs(10);
+const FAILURE_THRESHOLD: u32 = 5;
+const OPEN_DURATION: Duration = Duration::from_secs(30);
+
+static CIRCUITS: LazyLock<Mutex<HashMap<Endpoint, Circuit>>> = LazyLock::new(Mutex::default);
+
+#[derive(Debug)]
+pub enum RequestError {
+    Reqwest(reqwest::Error),
+    Unavailable(Unavailable),
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reqwest(err) => err.fmt(f),
+            Self::Unavailable(unavailable) => write!(
+                f,
+                "{} is unavailable for {} more seconds",
+                unavailable.endpoint.name(),
+                unavailable.retry_in.as_secs()
+            ),
+        }
+    }
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Reqwest(value)
+    }
+}
+
+impl From<Unavailable> for RequestError {
+    fn from(value: Unavailable) -> Self {
+        Self::Unavailable(value)
+    }
+}
+
+/// the circuit of the endpoint is open, so the request was not sent
+#[derive(Debug)]
+pub struct Unavailable {
+    pub endpoint: Endpoint,
+    pub retry_in: Duration,
+}
+
+/// counts consecutive failures of a service, and stays open for a while after
+/// too many of them. once that time passes, requests are let through again, and
+/// the next failure reopens it.
+#[derive(Default)]
+struct Circuit {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Circuit {
+    fn check(&self, now: Instant) -> Result<(), Duration> {
+        match self.open_until {
+            Some(open_until) if open_until > now => Err(open_until - now),
+            _ => Ok(()),
+        }
+    }
+
+    fn record(&mut self, success: bool, now: Instant) {
+        if success {
+            *self = Self::default();
+            return;
+        }
+
+        self.consecutive_failures += 1;
+
+        if self.consecutive_failures >= FAILURE_THRESHOLD {
+            self.open_until = Some(now + OPEN_DURATION);
+        }
+    }
+}
+
+/// fails if the circuit of the endpoint is open
+pub fn check_circuit(endpoint: Endpoint) -> Result<(), Unavailable> {
+    CIRCUITS
+        .lock()
+        .unwrap()
+        .get(&endpoint)
+        .map_or(Ok(()), |circuit| circuit.check(Instant::now()))
+        .map_err(|retry_in| {
+            metrics::record_api_error(endpoint);
+            Unavailable { endpoint, retry_in }
+        })
+}
+
+/// records the outcome of a request, counting network errors and server errors
+/// as failures
+fn record_result(endpoint: Endpoint, result: &reqwest::Result<Response>) {
+    match result {
+        Ok(response) => record_status(endpoint, response.status()),
+        Err(err) => record_error(endpoint, err),
+    }
+}
+
+pub fn record_status(endpoint: Endpoint, status: StatusCode) {
+    record(endpoint, !status.is_server_error());
+}
+
+pub fn record_error(endpoint: Endpoint, err: &reqwest::Error) {
+    record(endpoint, !(err.is_connect() || err.is_timeout()));
+}
+
+fn record(endpoint: Endpoint, success: bool) {
+    if !success {
+        metrics::record_api_error(endpoint);
+    }
+
+    let mut circuits = CIRCUITS.lock().unwrap();
+
+    if success {
+        circuits.remove(&endpoint);
+    } else {
+        circuits.entry(endpoint).or_default().record(false, Instant::now());
+    }
+}
+
+/// delays between attempts of a single request
+#[derive(Default)]
+pub struct Backoff {
+    retries: u32,
+}
+
+impl Backoff {
+    /// waits before the next attempt, either for the time the service asked
+    /// for, or for an exponential delay with jitter. returns whether the
+    /// request should be retried.
+    pub async fn wait(&mut self, requested_delay: Option<Duration>) -> bool {
+        if self.retries + 1 >= MAX_ATTEMPTS {
+            return false;
+        }
+
+        self.retries += 1;
+
+        let delay = match requested_delay {
+            Some(delay) if delay > MAX_DELAY => return false,
+            Some(delay) => delay,
+            None => backoff_delay(self.retries, rand::random_range(0.5..1.5)),
+        };
+
+        tokio::time::sleep(delay).await;
+
+        true
+    }
+}
+
+fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    BASE_DELAY.saturating_mul(1 << (attempt - 1).min(8)).mul_f64(jitter).min(MAX_DELAY)
+}
+
+/// the delay from a `Retry-After` header given in seconds
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers.get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[async_trait]
+pub trait SendRetrying {
+    /// sends the request, retrying it after network errors and responses which
+    /// say the service is temporarily unavailable. requests which aren't
+    /// idempotent are only retried when they surely weren't processed, and
+    /// requests with streamed bodies are never retried.
+    async fn send_retrying(self, endpoint: Endpoint) -> Result<Response, RequestError>;
+}
+
+#[async_trait]
+impl SendRetrying for RequestBuilder {
+    async fn send_retrying(self, endpoint: Endpoint) -> Result<Response, RequestError> {
+        let idempotent = self
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .is_some_and(|request| request.method().is_idempotent());
+
+        let mut backoff = Backoff::default();
+        let mut request = self;
+
+        let result = loop {
+            check_circuit(endpoint)?;
+
+            let next_request = request.try_clone();
+            let result = request.send().await;
+
+            let requested_delay = match &result {
+                Ok(response) => should_retry(response.status(), idempotent)
+                    .then(|| retry_after(response.headers())),
+                Err(err) => (err.is_connect() || (idempotent && err.is_timeout())).then_some(None),
+            };
+
+            let (Some(requested_delay), Some(next_request)) = (requested_delay, next_request)
+            else {
+                break result;
+            };
+
+            if !backoff.wait(requested_delay).await {
+                break result;
+            }
+
+            request = next_request;
+        };
+
+        // only the final outcome counts, so a single retried request can't open the
+        // circuit
+        record_result(endpoint, &result);
+
+        result.map_err(Into::into)
+    }
+}
+
+fn should_retry(status: StatusCode, idempotent: bool) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => true,
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::GATEWAY_TIMEOUT => idempotent,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_circuit() {
+        let now = Instant::now();
+        let mut circuit = Circuit::default();
+
+        for _ in 1..FAILURE_THRESHOLD {
+            circuit.record(false, now);
+        }
+
+        assert!(circuit.check(now).is_ok());
+
+        circuit.record(false, now);
+        assert_eq!(circuit.check(now), Err(OPEN_DURATION));
+        assert!(circuit.check(now + OPEN_DURATION).is_ok());
+
+        circuit.record(false, now + OPEN_DURATION);
+        assert!(circuit.check(now + OPEN_DURATION).is_err());
+
+        circuit.record(true, now);
+        assert!(circuit.check(now).is_ok());
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(1, 1.), BASE_DELAY);
+        assert_eq!(backoff_delay(2, 1.), BASE_DELAY * 2);
+        assert_eq!(backoff_delay(2, 0.5), BASE_DELAY);
+        assert_eq!(backoff_delay(20, 1.5), MAX_DELAY);
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "12".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(12)));
+
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use futures_util::future::AbortHandle;
+use tdlib::functions;
+
+use crate::apis::stablehorde;
+
+pub enum Cleanup {
+    DeleteMessage(i64),
+    StableHordeGeneration(String),
+}
+
+struct RunningCommand {
+    run_id: u64,
+    user_id: i64,
+    abort_handle: AbortHandle,
+    cleanups: Vec<Cleanup>,
+}
+
+/// commands currently being executed, keyed by the chat and message they were
+/// invoked with
+#[derive(Default)]
+pub struct RunningCommands {
+    commands: HashMap<(i64, i64), RunningCommand>,
+    next_run_id: u64,
+}
+
+impl RunningCommands {
+    /// returns the id of the run, and the cleanups of the previous run of the
+    /// same message, which is aborted, e.g. when the message was edited
+    /// before the command finished
+    pub fn insert(
+        &mut self,
+        chat_id: i64,
+        message_id: i64,
+        user_id: i64,
+        handle: AbortHandle,
+    ) -> (u64, Vec<Cleanup>) {
+        let run_id = self.next_run_id;
+        self.next_run_id += 1;
+
+        let previous = self.commands.insert(
+            (chat_id, message_id),
+            RunningCommand { run_id, user_id, abort_handle: handle, cleanups: Vec::new() },
+        );
+
+        let cleanups = previous
+            .map(|command| {
+                command.abort_handle.abort();
+                command.cleanups
+            })
+            .unwrap_or_default();
+
+        (run_id, cleanups)
+    }
+
+    /// removes the command, unless it was already replaced by a newer run
+    pub fn remove(&mut self, chat_id: i64, message_id: i64, run_id: u64) {
+        if self.commands.get(&(chat_id, message_id)).is_some_and(|command| command.run_id == run_id)
+        {
+            self.commands.remove(&(chat_id, message_id));
+        }
+    }
+
+    pub fn add_cleanup(&mut self, chat_id: i64, message_id: i64, cleanup: Cleanup) {
+        if let Some(command) = self.commands.get_mut(&(chat_id, message_id)) {
+            command.cleanups.push(cleanup);
+        }
+    }
+
+    /// aborts all commands of the user in the chat, except for the one invoked
+    /// with `message_id`
+    pub fn cancel(&mut self, chat_id: i64, user_id: i64, message_id: i64) -> Vec<Vec<Cleanup>> {
+        let keys = self
+            .commands
+            .iter()
+            .filter(|&(&key, command)| {
+                key.0 == chat_id && key.1 != message_id && command.user_id == user_id
+            })
+            .map(|(&key, _)| key)
+            .collect::<Vec<_>>();
+
+        keys.into_iter()
+            .filter_map(|key| self.commands.remove(&key))
+            .map(|command| {
+                command.abort_handle.abort();
+                command.cleanups
+            })
+            .collect()
+    }
+}
+
+impl Cleanup {
+    pub async fn run(self, chat_id: i64, http_client: reqwest::Client, client_id: i32) {
+        match self {
+            Self::DeleteMessage(message_id) => {
+                if let Err(err) =
+                    functions::delete_messages(chat_id, vec![message_id], true, client_id).await
+                {
+                    log::warn!("failed to delete a status message: {}", err.message);
+                }
+            }
+            Self::StableHordeGeneration(request_id) => {
+                if let Err(err) = stablehorde::cancel_generation(http_client, &request_id).await {
+                    log::warn!("failed to cancel a Stable Horde generation: {err}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rerun() {
+        let mut running_commands = RunningCommands::default();
+
+        let (_, first_handle) = AbortHandle::new_pair();
+        let (first, cleanups) = running_commands.insert(1, 2, 3, first_handle.clone());
+        assert!(cleanups.is_empty());
+        running_commands.add_cleanup(1, 2, Cleanup::DeleteMessage(4));
+
+        let (_, second_handle) = AbortHandle::new_pair();
+        let (second, cleanups) = running_commands.insert(1, 2, 3, second_handle.clone());
+        assert!(first_handle.is_aborted());
+        assert!(matches!(cleanups[..], [Cleanup::DeleteMessage(4)]));
+
+        // the aborted run finishing must not unregister the new one
+        running_commands.remove(1, 2, first);
+        assert_eq!(running_commands.cancel(1, 3, 0).len(), 1);
+        assert!(second_handle.is_aborted());
+
+        running_commands.remove(1, 2, second);
+        assert!(running_commands.commands.is_empty());
+    }
+}
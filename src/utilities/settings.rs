@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::{env, fmt, fs, io};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use url::Url;
+
+use super::endpoints::Endpoint;
+
+const FILENAME: &str = "settings.json";
+
+// keys which can be overridden by environment variables with uppercase names
+const ENV_OVERRIDES: [&str; 18] = [
+    "telegram_token",
+    "api_id",
+    "api_hash",
+    "db_encryption_key",
+    "user_agent",
+    "metrics_address",
+    "cobalt_instances",
+    "openrouter_api_key",
+    "fal_api_key",
+    "stablehorde_token",
+    "stablehorde_client",
+    "makersuite_api_key",
+    "groq_api_key",
+    "persist_rate_limits",
+    "owners",
+    "operators",
+    "models",
+    "endpoints",
+];
+
+// overrides which are parsed as JSON instead of plain strings
+const JSON_ENV_OVERRIDES: [&str; 7] = [
+    "api_id",
+    "cobalt_instances",
+    "persist_rate_limits",
+    "owners",
+    "operators",
+    "models",
+    "endpoints",
+];
+
+static SETTINGS: LazyLock<RwLock<Arc<Settings>>> = LazyLock::new(RwLock::default);
+
+#[derive(Default, Deserialize)]
+pub struct Settings {
+    pub telegram_token: String,
+    pub api_id: i32,
+    pub api_hash: String,
+    pub db_encryption_key: String,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub metrics_address: Option<String>,
+    #[serde(default)]
+    pub cobalt_instances: Vec<CobaltInstance>,
+    #[serde(default)]
+    pub openrouter_api_key: String,
+    #[serde(default)]
+    pub fal_api_key: String,
+    #[serde(default)]
+    pub stablehorde_token: String,
+    #[serde(default)]
+    pub stablehorde_client: String,
+    #[serde(default)]
+    pub makersuite_api_key: String,
+    #[serde(default)]
+    pub groq_api_key: String,
+    #[serde(default)]
+    pub persist_rate_limits: bool,
+    #[serde(default)]
+    pub owners: Vec<i64>,
+    #[serde(default)]
+    pub operators: Vec<i64>,
+    #[serde(default = "default_models")]
+    pub models: Vec<ModelSettings>,
+    /// base URLs of external APIs by endpoint name, replacing the defaults
+    #[serde(default)]
+    pub endpoints: HashMap<String, String>,
+}
+
+/// what a user is allowed to do with the bot, ordered by privilege
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Role {
+    User,
+    Operator,
+    Owner,
+}
+
+#[derive(Deserialize)]
+pub struct CobaltInstance {
+    pub name: String,
+    pub url: String,
+    pub api_key: Option<String>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelProvider {
+    Gemini,
+    Groq,
+    OpenRouter,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct ModelSettings {
+    pub provider: ModelProvider,
+    pub command_names: Vec<String>,
+    pub description: String,
+    pub model: String,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u16,
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit: (u32, u32),
+    #[serde(default)]
+    pub thinking_markers: Option<(String, String)>,
+}
+
+impl ModelSettings {
+    fn new(
+        provider: ModelProvider,
+        command_names: &[&str],
+        description: &str,
+        model: &str,
+    ) -> Self {
+        Self {
+            provider,
+            command_names: command_names.iter().map(|&name| name.into()).collect(),
+            description: description.into(),
+            model: model.into(),
+            max_tokens: default_max_tokens(),
+            rate_limit: default_rate_limit(),
+            thinking_markers: None,
+        }
+    }
+
+    // model commands are registered once and live until the bot exits
+    pub fn leak_command_names(&self) -> &'static [&'static str] {
+        self.command_names.iter().map(|name| &*name.clone().leak()).collect::<Vec<_>>().leak()
+    }
+
+    pub fn leak_description(&self) -> &'static str {
+        self.description.clone().leak()
+    }
+}
+
+const fn default_max_tokens() -> u16 {
+    512
+}
+
+const fn default_rate_limit() -> (u32, u32) {
+    (4, 60)
+}
+
+fn default_models() -> Vec<ModelSettings> {
+    use ModelProvider::{Gemini, Groq, OpenRouter};
+
+    vec![
+        ModelSettings::new(
+            Gemini,
+            &["gemini", "g", "gemini2", "g2"],
+            "ask Gemini 2.5 Flash",
+            "gemini-2.5-flash",
+        ),
+        ModelSettings::new(
+            Gemini,
+            &["gemini_lite", "gl"],
+            "ask Gemini 2.5 Flash-Lite",
+            "gemini-2.5-flash-lite",
+        ),
+        ModelSettings {
+            max_tokens: 1024,
+            ..ModelSettings::new(
+                Groq,
+                &["gpt_oss", "gpt"],
+                "ask GPT OSS 120B",
+                "openai/gpt-oss-120b",
+            )
+        },
+        ModelSettings::new(
+            Groq,
+            &["llama4", "llama"],
+            "ask Llama 4 Maverick",
+            "meta-llama/llama-4-maverick-17b-128e-instruct",
+        ),
+        ModelSettings::new(Groq, &["llama3"], "ask Llama 3.3 70B", "llama-3.3-70b-versatile"),
+        ModelSettings::new(
+            Groq,
+            &["kimi_k2", "k2"],
+            "ask Kimi K2 0905",
+            "moonshotai/kimi-k2-instruct-0905",
+        ),
+        ModelSettings {
+            rate_limit: (6, 60),
+            ..ModelSettings::new(
+                OpenRouter,
+                &["mistral"],
+                "ask Mistral Small 3",
+                "mistralai/mistral-small-24b-instruct-2501",
+            )
+        },
+        ModelSettings {
+            rate_limit: (2, 120),
+            ..ModelSettings::new(
+                OpenRouter,
+                &["perplexity", "sonar"],
+                "ask Perplexity Sonar",
+                "perplexity/sonar",
+            )
+        },
+    ]
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    EnvOverride(String, serde_json::Error),
+    Invalid(serde_json::Error),
+    Model(String, &'static str),
+    DuplicateCommandName(String),
+    Endpoint(String),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read {FILENAME}: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse {FILENAME}: {err}"),
+            Self::EnvOverride(name, err) => write!(f, "invalid value of {name}: {err}"),
+            Self::Invalid(err) => write!(f, "invalid settings: {err}"),
+            Self::Model(model, reason) => write!(f, "model {model} {reason}"),
+            Self::DuplicateCommandName(name) => {
+                write!(f, "command name {name} is used more than once")
+            }
+            Self::Endpoint(name) => write!(f, "endpoint {name} is unknown or has an invalid URL"),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, SettingsError> {
+        let mut values = match fs::read_to_string(FILENAME) {
+            Ok(text) => {
+                serde_json::from_str::<Map<String, Value>>(&text).map_err(SettingsError::Parse)?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                log::debug!("{FILENAME} not found, using environment variables only");
+                Map::new()
+            }
+            Err(err) => return Err(SettingsError::Io(err)),
+        };
+
+        for key in ENV_OVERRIDES {
+            let name = key.to_ascii_uppercase();
+
+            let Ok(value) = env::var(&name) else {
+                continue;
+            };
+
+            let value = if JSON_ENV_OVERRIDES.contains(&key) {
+                serde_json::from_str(&value).map_err(|err| SettingsError::EnvOverride(name, err))?
+            } else {
+                Value::String(value)
+            };
+
+            values.insert(key.into(), value);
+        }
+
+        let settings = serde_json::from_value::<Self>(Value::Object(values))
+            .map_err(SettingsError::Invalid)?;
+
+        let mut command_names = HashSet::new();
+
+        for model in &settings.models {
+            if let Some(name) = model.command_names.iter().find(|name| !command_names.insert(*name))
+            {
+                return Err(SettingsError::DuplicateCommandName(name.clone()));
+            }
+
+            if model.command_names.is_empty() {
+                return Err(SettingsError::Model(model.model.clone(), "has no command names"));
+            }
+
+            if model.rate_limit.0 == 0 || model.rate_limit.1 == 0 {
+                return Err(SettingsError::Model(
+                    model.model.clone(),
+                    "needs a positive rate limit capacity and period",
+                ));
+            }
+        }
+
+        if let Some(name) = settings.endpoints.iter().find_map(|(name, url)| {
+            (Endpoint::from_name(name).is_none() || Url::parse(url).is_err()).then_some(name)
+        }) {
+            return Err(SettingsError::Endpoint(name.clone()));
+        }
+
+        for (key, value) in [
+            ("openrouter_api_key", &settings.openrouter_api_key),
+            ("fal_api_key", &settings.fal_api_key),
+            ("stablehorde_token", &settings.stablehorde_token),
+            ("stablehorde_client", &settings.stablehorde_client),
+            ("makersuite_api_key", &settings.makersuite_api_key),
+            ("groq_api_key", &settings.groq_api_key),
+        ] {
+            if value.is_empty() {
+                log::warn!("{key} is not set, commands using it won't work");
+            }
+        }
+
+        Ok(settings)
+    }
+
+    pub fn role(&self, user_id: i64) -> Role {
+        if self.owners.contains(&user_id) {
+            Role::Owner
+        } else if self.operators.contains(&user_id) {
+            Role::Operator
+        } else {
+            Role::User
+        }
+    }
+}
+
+pub fn get() -> Arc<Settings> {
+    SETTINGS.read().unwrap().clone()
+}
+
+pub fn set(settings: Settings) {
+    *SETTINGS.write().unwrap() = Arc::new(settings);
+}
+
+pub fn reload() -> Result<(), SettingsError> {
+    set(Settings::load()?);
+    log::info!("settings reloaded");
+
+    Ok(())
+}
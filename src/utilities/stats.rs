@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::persistence::Persistent;
+
+const HOUR: i64 = 60 * 60;
+const HOURLY_RETENTION: i64 = 7 * 24;
+
+// upper bounds of the latency histogram buckets, in milliseconds
+const LATENCY_BUCKETS: [u32; 12] =
+    [100, 250, 500, 1000, 2500, 5000, 10_000, 30_000, 60_000, 120_000, 300_000, u32::MAX];
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandStats {
+    pub invocations: u64,
+    /// users who ran the command, empty in all-time stats to keep them from
+    /// growing forever
+    pub users: HashSet<i64>,
+    pub errors: HashMap<String, u64>,
+    latencies: [u64; LATENCY_BUCKETS.len()],
+}
+
+impl CommandStats {
+    fn record(&mut self, user_id: Option<i64>, latency: Duration, error: Option<&str>) {
+        self.invocations += 1;
+        self.users.extend(user_id);
+
+        if let Some(error) = error {
+            *self.errors.entry(error.into()).or_default() += 1;
+        }
+
+        let latency = u32::try_from(latency.as_millis()).unwrap_or(u32::MAX);
+        let bucket = LATENCY_BUCKETS.iter().position(|&bound| latency <= bound).unwrap();
+        self.latencies[bucket] += 1;
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.invocations += other.invocations;
+        self.users.extend(&other.users);
+
+        for (error, count) in &other.errors {
+            *self.errors.entry(error.clone()).or_default() += count;
+        }
+
+        for (latency, other) in self.latencies.iter_mut().zip(other.latencies) {
+            *latency += other;
+        }
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// upper bound of the given latency percentile in milliseconds,
+    /// `u32::MAX` if it's longer than the largest bucket
+    pub fn latency_percentile(&self, percentile: u64) -> Option<u32> {
+        let total = self.latencies.iter().sum::<u64>();
+
+        if total == 0 {
+            return None;
+        }
+
+        let target = (total * percentile).div_ceil(100);
+        let mut count = 0;
+
+        for (bound, latencies) in LATENCY_BUCKETS.into_iter().zip(self.latencies) {
+            count += latencies;
+            if count >= target {
+                return Some(bound);
+            }
+        }
+
+        None
+    }
+}
+
+/// command usage statistics, kept in hourly buckets for a week and summed up
+/// for all time. unique users are only counted in the hourly buckets.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    all_time: HashMap<String, CommandStats>,
+    hourly: BTreeMap<i64, HashMap<String, CommandStats>>,
+}
+
+impl Persistent for Stats {
+    const KEY: &'static str = "stats";
+    const NAME: &'static str = "command stats";
+    const VERSION: u32 = 1;
+
+    fn create() -> Self {
+        Self::default()
+    }
+}
+
+impl Stats {
+    pub fn record(
+        &mut self,
+        command: &str,
+        user_id: i64,
+        latency: Duration,
+        error: Option<&str>,
+        now: i64,
+    ) {
+        let hour = now / HOUR;
+        self.hourly = self.hourly.split_off(&(hour - HOURLY_RETENTION));
+
+        self.all_time.entry(command.into()).or_default().record(None, latency, error);
+        self.hourly.entry(hour).or_default().entry(command.into()).or_default().record(
+            Some(user_id),
+            latency,
+            error,
+        );
+    }
+
+    /// stats of commands run since the given timestamp, at most a week ago
+    pub fn since(&self, since: i64) -> HashMap<String, CommandStats> {
+        let mut stats = HashMap::<String, CommandStats>::new();
+
+        for commands in self.hourly.range(since / HOUR..).map(|(_, commands)| commands) {
+            for (command, command_stats) in commands {
+                stats.entry(command.clone()).or_default().merge(command_stats);
+            }
+        }
+
+        stats
+    }
+
+    pub fn all_time(&self) -> &HashMap<String, CommandStats> {
+        &self.all_time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::default();
+        let now = 100 * HOURLY_RETENTION * HOUR;
+
+        stats.record("ping", 1, Duration::from_millis(50), None, now - 8 * 24 * HOUR);
+        stats.record("ping", 1, Duration::from_millis(50), None, now - 2 * HOUR);
+        stats.record("ping", 2, Duration::from_millis(700), Some("Server"), now);
+        stats.record("gemini", 1, Duration::from_secs(4), None, now);
+
+        assert_eq!(stats.all_time()["ping"].invocations, 3);
+        assert!(stats.all_time()["ping"].users.is_empty());
+        assert_eq!(stats.hourly.len(), 2);
+
+        let day = stats.since(now - 24 * HOUR);
+        assert_eq!(day["ping"].invocations, 2);
+        assert_eq!(day["ping"].users.len(), 2);
+        assert_eq!(day["ping"].failures(), 1);
+        assert_eq!(day["ping"].latency_percentile(50), Some(100));
+        assert_eq!(day["ping"].latency_percentile(95), Some(1000));
+        assert_eq!(day["gemini"].latency_percentile(50), Some(5000));
+
+        assert_eq!(stats.since(now - HOUR)["ping"].invocations, 1);
+    }
+}
@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::sync::Mutex;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+const FILENAME: &str = "bot.db";
+
+pub type StorageError = Box<dyn Error + Send + Sync>;
+
+/// a store of versioned blobs which persistent state is saved to
+pub trait Storage: Send + Sync {
+    fn load(&self, key: &str) -> Result<Option<(u32, Vec<u8>)>, StorageError>;
+    fn save(&self, key: &str, version: u32, data: &[u8]) -> Result<(), StorageError>;
+
+    /// whether state missing from the storage is imported from the file it was
+    /// saved to before
+    fn imports_files(&self) -> bool {
+        true
+    }
+}
+
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    pub fn open() -> Result<Self, StorageError> {
+        let connection = Connection::open(FILENAME)?;
+
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.execute(
+            concat!(
+                "CREATE TABLE IF NOT EXISTS state (",
+                "key TEXT PRIMARY KEY, version INTEGER NOT NULL, data BLOB NOT NULL)"
+            ),
+            (),
+        )?;
+
+        Ok(Self { connection: Mutex::new(connection) })
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load(&self, key: &str) -> Result<Option<(u32, Vec<u8>)>, StorageError> {
+        let row = self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT version, data FROM state WHERE key = ?1", [key], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .optional()?;
+
+        Ok(row)
+    }
+
+    fn save(&self, key: &str, version: u32, data: &[u8]) -> Result<(), StorageError> {
+        self.connection.lock().unwrap().execute(
+            concat!(
+                "INSERT INTO state (key, version, data) VALUES (?1, ?2, ?3) ",
+                "ON CONFLICT (key) DO UPDATE SET version = excluded.version, data = excluded.data"
+            ),
+            params![key, version, data],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use tdlib::enums::{
+    self, ChatAction, InputInlineQueryResult, InputMessageContent, InputMessageReplyTo, ReplyMarkup,
+};
+use tdlib::functions;
+use tdlib::types::Message;
+
+use crate::bot::TdResult;
+
+/// the TDLib requests made by command, callback and inline query contexts,
+/// which tests can replace with a fake
+#[async_trait]
+pub trait TelegramClient: Send + Sync {
+    async fn send_message(
+        &self,
+        chat_id: i64,
+        topic_id: Option<enums::MessageTopic>,
+        reply_to: Option<InputMessageReplyTo>,
+        reply_markup: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message>;
+
+    async fn edit_message_text(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        reply_markup: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message>;
+
+    async fn delete_messages(&self, chat_id: i64, message_ids: Vec<i64>) -> TdResult<()>;
+
+    async fn get_replied_message(&self, chat_id: i64, message_id: i64) -> TdResult<Message>;
+
+    async fn send_chat_action(
+        &self,
+        chat_id: i64,
+        topic_id: enums::MessageTopic,
+        action: ChatAction,
+    ) -> TdResult<()>;
+
+    async fn answer_callback_query(
+        &self,
+        query_id: i64,
+        text: String,
+        show_alert: bool,
+    ) -> TdResult<()>;
+
+    async fn answer_inline_query(
+        &self,
+        query_id: i64,
+        results: Vec<InputInlineQueryResult>,
+        cache_time: i32,
+    ) -> TdResult<()>;
+}
+
+pub struct TdlibClient {
+    client_id: i32,
+}
+
+impl TdlibClient {
+    pub const fn new(client_id: i32) -> Self {
+        Self { client_id }
+    }
+}
+
+#[async_trait]
+impl TelegramClient for TdlibClient {
+    async fn send_message(
+        &self,
+        chat_id: i64,
+        topic_id: Option<enums::MessageTopic>,
+        reply_to: Option<InputMessageReplyTo>,
+        reply_markup: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message> {
+        let enums::Message::Message(message) = functions::send_message(
+            chat_id,
+            topic_id,
+            reply_to,
+            None,
+            reply_markup,
+            content,
+            self.client_id,
+        )
+        .await?;
+
+        Ok(message)
+    }
+
+    async fn edit_message_text(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        reply_markup: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message> {
+        let enums::Message::Message(message) = functions::edit_message_text(
+            chat_id,
+            message_id,
+            reply_markup,
+            content,
+            self.client_id,
+        )
+        .await?;
+
+        Ok(message)
+    }
+
+    async fn delete_messages(&self, chat_id: i64, message_ids: Vec<i64>) -> TdResult<()> {
+        functions::delete_messages(chat_id, message_ids, true, self.client_id).await
+    }
+
+    async fn get_replied_message(&self, chat_id: i64, message_id: i64) -> TdResult<Message> {
+        let enums::Message::Message(message) =
+            functions::get_replied_message(chat_id, message_id, self.client_id).await?;
+
+        Ok(message)
+    }
+
+    async fn send_chat_action(
+        &self,
+        chat_id: i64,
+        topic_id: enums::MessageTopic,
+        action: ChatAction,
+    ) -> TdResult<()> {
+        functions::send_chat_action(chat_id, topic_id, String::new(), Some(action), self.client_id)
+            .await
+    }
+
+    async fn answer_callback_query(
+        &self,
+        query_id: i64,
+        text: String,
+        show_alert: bool,
+    ) -> TdResult<()> {
+        functions::answer_callback_query(
+            query_id,
+            text,
+            show_alert,
+            String::new(),
+            0,
+            self.client_id,
+        )
+        .await
+    }
+
+    async fn answer_inline_query(
+        &self,
+        query_id: i64,
+        results: Vec<InputInlineQueryResult>,
+        cache_time: i32,
+    ) -> TdResult<()> {
+        functions::answer_inline_query(
+            query_id,
+            true,
+            None,
+            results,
+            cache_time,
+            String::new(),
+            self.client_id,
+        )
+        .await
+    }
+}
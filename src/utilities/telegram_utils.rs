@@ -1,17 +1,19 @@
 use std::borrow::Cow;
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use tdlib::enums::{
-    self, ChatMemberStatus, ChatType, InlineKeyboardButtonType, MessageContent, MessageReplyTo,
+    ChatMemberStatus, ChatType, InlineKeyboardButtonType, MessageContent, MessageReplyTo,
     ReplyMarkup, StickerFormat,
 };
-use tdlib::functions;
 use tdlib::types::{
     Animation, Audio, ChatPhoto, Document, File, FormattedText, InlineKeyboardButton,
-    InlineKeyboardButtonTypeUrl, Message, Photo, PhotoSize, ReplyMarkupInlineKeyboard, Sticker,
-    UpdateChatMember, User, Video, VideoNote, VoiceNote,
+    InlineKeyboardButtonTypeCallback, InlineKeyboardButtonTypeUrl, Message, Photo, PhotoSize,
+    ReplyMarkupInlineKeyboard, Sticker, UpdateChatMember, User, Video, VideoNote, VoiceNote,
 };
 
 use super::cache::CompactChat;
+use super::telegram_client::TelegramClient;
 use crate::bot::TdResult;
 
 pub trait MainUsername {
@@ -174,7 +176,7 @@ fn largest_photo(sizes: &[PhotoSize]) -> Option<&File> {
 pub async fn get_message_or_reply_attachment(
     message: &Message,
     include_non_images: bool,
-    client_id: i32,
+    client: &dyn TelegramClient,
 ) -> TdResult<Option<MessageAttachment<'_>>> {
     if let Some(attachment) =
         get_message_attachment(Cow::Borrowed(&message.content), include_non_images)
@@ -189,9 +191,7 @@ pub async fn get_message_or_reply_attachment(
     let content = if let Some(content) = reply.content.as_ref() {
         Cow::Borrowed(content)
     } else {
-        let Some(enums::Message::Message(message)) =
-            functions::get_replied_message(message.chat_id, message.id, client_id).await.ok()
-        else {
+        let Ok(message) = client.get_replied_message(message.chat_id, message.id).await else {
             return Ok(None);
         };
 
@@ -210,6 +210,26 @@ pub fn donate_markup(name: &str, url: impl Into<String>) -> ReplyMarkup {
     })
 }
 
+pub fn callback_button(
+    text: impl Into<String>,
+    command_name: &str,
+    payload: &str,
+) -> InlineKeyboardButton {
+    InlineKeyboardButton {
+        text: text.into(),
+        r#type: InlineKeyboardButtonType::Callback(InlineKeyboardButtonTypeCallback {
+            data: STANDARD.encode(format!("{command_name} {payload}")),
+        }),
+    }
+}
+
+pub fn parse_callback_data(data: &str) -> Option<(String, String)> {
+    let data = String::from_utf8(STANDARD.decode(data).ok()?).ok()?;
+    let (command_name, payload) = data.split_once(' ')?;
+
+    Some((command_name.into(), payload.into()))
+}
+
 pub fn log_status_update(update: &UpdateChatMember, chat: &CompactChat) {
     if let ChatType::Private(_) = chat.r#type {
         return;
@@ -227,3 +247,21 @@ pub fn log_status_update(update: &UpdateChatMember, chat: &CompactChat) {
 
     log::info!("{status} {chat}");
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_callback_data() {
+        let InlineKeyboardButtonType::Callback(callback) =
+            callback_button("text", "kebab", "again").r#type
+        else {
+            panic!("expected a callback button");
+        };
+
+        assert_eq!(parse_callback_data(&callback.data), Some(("kebab".into(), "again".into())));
+        assert_eq!(parse_callback_data("not base64!"), None);
+        assert_eq!(parse_callback_data(&STANDARD.encode("kebab")), None);
+    }
+}
@@ -1,19 +1,42 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
 
-use tdlib::enums::{ChatType, MessageContent, MessageSender, UserType};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use tdlib::enums::{
+    self, ChatAction, ChatType, InputInlineQueryResult, InputMessageContent, InputMessageReplyTo,
+    MessageContent, MessageSender, ReplyMarkup, UserType,
+};
 use tdlib::types::{
     ChatPermissions, ChatTypeSupergroup, FormattedText, Message, MessageSenderUser, MessageText,
+    UpdateMessageSendSucceeded,
 };
+use tokio::io::{self, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
 
 use super::bot_state::BotState;
 use super::cache::{CompactChat, CompactUser};
 use super::command_context::CommandContext;
+use super::endpoints::Endpoint;
+use super::storage::{Storage, StorageError};
+use super::telegram_client::TelegramClient;
+use crate::bot::{TdError, TdResult};
 
 pub fn command_context() -> CommandContext {
+    fake_command_context().0
+}
+
+/// a command context which sends its requests to the returned fake client
+pub fn fake_command_context() -> (CommandContext, Arc<FakeClient>) {
     dotenvy::dotenv().ok();
 
-    CommandContext {
+    let bot_state = Arc::new(BotState::with_storage(Box::new(MemoryStorage::default())).unwrap());
+    let client = Arc::new(FakeClient::new(bot_state.clone()));
+
+    let ctx = CommandContext {
         client_id: 0,
+        client: client.clone(),
         chat: CompactChat {
             r#type: ChatType::Supergroup(ChatTypeSupergroup::default()),
             title: "chat_title".into(),
@@ -27,49 +50,354 @@ pub fn command_context() -> CommandContext {
             r#type: UserType::Regular,
             language_code: "user_language_code".into(),
         },
-        message: Message {
-            id: 0,
-            sender_id: MessageSender::User(MessageSenderUser::default()),
-            chat_id: 0,
-            sending_state: None,
-            scheduling_state: None,
-            is_outgoing: false,
-            is_pinned: false,
-            is_from_offline: false,
-            can_be_saved: false,
-            has_timestamped_media: false,
-            is_channel_post: false,
-            is_paid_star_suggested_post: false,
-            is_paid_ton_suggested_post: false,
-            contains_unread_mention: false,
-            date: 0,
-            edit_date: 0,
-            forward_info: None,
-            import_info: None,
-            interaction_info: None,
-            unread_reactions: Vec::new(),
-            fact_check: None,
-            suggested_post_info: None,
-            reply_to: None,
-            topic_id: None,
-            self_destruct_type: None,
-            self_destruct_in: 0.,
-            auto_delete_in: 0.,
-            via_bot_user_id: 0,
-            sender_business_bot_user_id: 0,
-            sender_boost_count: 0,
-            paid_message_star_count: 0,
-            author_signature: "message_author_signature".into(),
-            media_album_id: 0,
-            effect_id: 0,
-            restriction_info: None,
-            content: MessageContent::MessageText(MessageText {
-                text: FormattedText { text: "message_content_text".into(), ..Default::default() },
-                link_preview: None,
-                link_preview_options: None,
-            }),
-            reply_markup: None,
-        },
-        bot_state: Arc::new(BotState::new()),
+        message: text_message(0, 0, "message_content_text"),
+        bot_state,
+    };
+
+    // like TDLib, which sends updates about users before their messages
+    ctx.bot_state.cache.lock().unwrap().insert_user(ctx.user.clone());
+
+    (ctx, client)
+}
+
+/// keeps the state of a single test instead of writing it to `bot.db`
+#[derive(Default)]
+pub struct MemoryStorage {
+    state: Mutex<HashMap<String, (u32, Vec<u8>)>>,
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Result<Option<(u32, Vec<u8>)>, StorageError> {
+        Ok(self.state.lock().unwrap().get(key).cloned())
     }
+
+    fn save(&self, key: &str, version: u32, data: &[u8]) -> Result<(), StorageError> {
+        self.state.lock().unwrap().insert(key.into(), (version, data.into()));
+        Ok(())
+    }
+
+    fn imports_files(&self) -> bool {
+        false
+    }
+}
+
+pub fn text_message(chat_id: i64, id: i64, text: &str) -> Message {
+    message(
+        chat_id,
+        id,
+        MessageContent::MessageText(MessageText {
+            text: FormattedText { text: text.into(), ..Default::default() },
+            link_preview: None,
+            link_preview_options: None,
+        }),
+    )
+}
+
+pub fn message(chat_id: i64, id: i64, content: MessageContent) -> Message {
+    Message {
+        id,
+        sender_id: MessageSender::User(MessageSenderUser::default()),
+        chat_id,
+        sending_state: None,
+        scheduling_state: None,
+        is_outgoing: false,
+        is_pinned: false,
+        is_from_offline: false,
+        can_be_saved: false,
+        has_timestamped_media: false,
+        is_channel_post: false,
+        is_paid_star_suggested_post: false,
+        is_paid_ton_suggested_post: false,
+        contains_unread_mention: false,
+        date: 0,
+        edit_date: 0,
+        forward_info: None,
+        import_info: None,
+        interaction_info: None,
+        unread_reactions: Vec::new(),
+        fact_check: None,
+        suggested_post_info: None,
+        reply_to: None,
+        topic_id: None,
+        self_destruct_type: None,
+        self_destruct_in: 0.,
+        auto_delete_in: 0.,
+        via_bot_user_id: 0,
+        sender_business_bot_user_id: 0,
+        sender_boost_count: 0,
+        paid_message_star_count: 0,
+        author_signature: "message_author_signature".into(),
+        media_album_id: 0,
+        effect_id: 0,
+        restriction_info: None,
+        content,
+        reply_markup: None,
+    }
+}
+
+pub struct SentMessage {
+    pub chat_id: i64,
+    pub message_id: i64,
+    pub content: InputMessageContent,
+}
+
+impl SentMessage {
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            InputMessageContent::InputMessageText(content) => Some(&content.text.text),
+            _ => None,
+        }
+    }
+}
+
+/// records the requests instead of sending them to Telegram, and reports sent
+/// messages to the bot state the same way TDLib updates would
+pub struct FakeClient {
+    bot_state: Arc<BotState>,
+    next_message_id: AtomicI64,
+    pub sent: Mutex<Vec<SentMessage>>,
+    pub edited: Mutex<Vec<SentMessage>>,
+    pub chat_actions: Mutex<Vec<ChatAction>>,
+    pub callback_answers: Mutex<Vec<String>>,
+    pub inline_answers: Mutex<Vec<InputInlineQueryResult>>,
+    replied_messages: Mutex<HashMap<(i64, i64), Message>>,
+}
+
+impl FakeClient {
+    pub fn new(bot_state: Arc<BotState>) -> Self {
+        Self {
+            bot_state,
+            next_message_id: AtomicI64::new(1),
+            sent: Mutex::default(),
+            edited: Mutex::default(),
+            chat_actions: Mutex::default(),
+            callback_answers: Mutex::default(),
+            inline_answers: Mutex::default(),
+            replied_messages: Mutex::default(),
+        }
+    }
+
+    /// makes `get_replied_message` return the message for the given one
+    pub fn set_replied_message(&self, chat_id: i64, message_id: i64, replied_message: Message) {
+        self.replied_messages.lock().unwrap().insert((chat_id, message_id), replied_message);
+    }
+
+    pub fn sent_texts(&self) -> Vec<String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| message.text().map(Into::into))
+            .collect()
+    }
+
+    fn message_id(&self) -> i64 {
+        self.next_message_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+fn message_content(content: &InputMessageContent) -> MessageContent {
+    let text = match content {
+        InputMessageContent::InputMessageText(content) => content.text.clone(),
+        _ => FormattedText::default(),
+    };
+
+    MessageContent::MessageText(MessageText {
+        text,
+        link_preview: None,
+        link_preview_options: None,
+    })
+}
+
+#[async_trait]
+impl TelegramClient for FakeClient {
+    async fn send_message(
+        &self,
+        chat_id: i64,
+        _: Option<enums::MessageTopic>,
+        _: Option<InputMessageReplyTo>,
+        _: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message> {
+        let pending_message = message(chat_id, self.message_id(), message_content(&content));
+        let sent_message = message(chat_id, self.message_id(), message_content(&content));
+
+        self.sent.lock().unwrap().push(SentMessage {
+            chat_id,
+            message_id: sent_message.id,
+            content,
+        });
+
+        // like TDLib, report the message as sent after the request returns
+        let bot_state = self.bot_state.clone();
+        let old_message_id = pending_message.id;
+
+        tokio::spawn(async move {
+            bot_state.command_replies.lock().unwrap().message_sent(
+                chat_id,
+                old_message_id,
+                sent_message.id,
+            );
+
+            bot_state.message_queue.message_sent(Ok(UpdateMessageSendSucceeded {
+                message: sent_message,
+                old_message_id,
+            }));
+        });
+
+        Ok(pending_message)
+    }
+
+    async fn edit_message_text(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        _: Option<ReplyMarkup>,
+        content: InputMessageContent,
+    ) -> TdResult<Message> {
+        let edited_message = message(chat_id, message_id, message_content(&content));
+
+        self.edited.lock().unwrap().push(SentMessage { chat_id, message_id, content });
+
+        Ok(edited_message)
+    }
+
+    async fn delete_messages(&self, _: i64, _: Vec<i64>) -> TdResult<()> {
+        Ok(())
+    }
+
+    async fn get_replied_message(&self, chat_id: i64, message_id: i64) -> TdResult<Message> {
+        self.replied_messages
+            .lock()
+            .unwrap()
+            .get(&(chat_id, message_id))
+            .cloned()
+            .ok_or_else(|| TdError { code: 404, message: "Not Found".into() })
+    }
+
+    async fn send_chat_action(
+        &self,
+        _: i64,
+        _: enums::MessageTopic,
+        action: ChatAction,
+    ) -> TdResult<()> {
+        self.chat_actions.lock().unwrap().push(action);
+        Ok(())
+    }
+
+    async fn answer_callback_query(&self, _: i64, text: String, _: bool) -> TdResult<()> {
+        self.callback_answers.lock().unwrap().push(text);
+        Ok(())
+    }
+
+    async fn answer_inline_query(
+        &self,
+        _: i64,
+        results: Vec<InputInlineQueryResult>,
+        _: i32,
+    ) -> TdResult<()> {
+        self.inline_answers.lock().unwrap().extend(results);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct RecordedRequest {
+    pub target: String,
+    pub body: String,
+}
+
+/// a local stand-in for an external API, which answers every request with a
+/// recorded response
+pub struct FixtureServer {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl FixtureServer {
+    /// starts the server and points the endpoint at it for requests made on the
+    /// current thread
+    pub async fn start(
+        endpoint: Endpoint,
+        status: StatusCode,
+        content_type: &'static str,
+        body: &'static str,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        endpoint.set_test_override(format!("http://{}", listener.local_addr().unwrap()));
+
+        tokio::spawn({
+            let requests = requests.clone();
+
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    respond(stream, &requests, status, content_type, body).await.unwrap();
+                }
+            }
+        });
+
+        Self { requests }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// an HTTP client which connects to fixture servers directly, ignoring proxy
+/// variables
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder().no_proxy().build().unwrap()
+}
+
+async fn respond(
+    stream: TcpStream,
+    requests: &Mutex<Vec<RecordedRequest>>,
+    status: StatusCode,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    let mut stream = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    stream.read_line(&mut request_line).await?;
+
+    let mut content_length = 0;
+
+    loop {
+        let mut header = String::new();
+        stream.read_line(&mut header).await?;
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap();
+        }
+    }
+
+    let mut request_body = vec![0; content_length];
+    stream.read_exact(&mut request_body).await?;
+
+    // record the request before responding, so it's visible once the client gets
+    // the response
+    requests.lock().unwrap().push(RecordedRequest {
+        target: request_line.split(' ').nth(1).unwrap_or_default().into(),
+        body: String::from_utf8_lossy(&request_body).into_owned(),
+    });
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\n\
+         connection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let stream = stream.get_mut();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
 }
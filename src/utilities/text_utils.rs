@@ -1,3 +1,5 @@
+use super::i18n::{self, Language};
+
 pub trait TruncateWithEllipsis {
     fn truncate_with_ellipsis(self, max_len: usize) -> Self;
 }
@@ -50,11 +52,11 @@ pub fn progress_bar(current: u32, max: u32) -> String {
     progress
 }
 
-pub fn check_prompt(prompt: &str) -> Option<&'static str> {
+pub fn check_prompt(language: Language, prompt: &str) -> Option<&'static str> {
     if prompt.chars().count() > 1024 {
-        Some("this prompt is too long (>1024).")
+        Some(i18n::text(language, "prompt_too_long"))
     } else if prompt.lines().count() > 8 {
-        Some("this prompt has too many lines (>8).")
+        Some(i18n::text(language, "prompt_too_many_lines"))
     } else {
         None
     }
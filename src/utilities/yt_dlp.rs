@@ -38,6 +38,7 @@ pub async fn get_infojson(
 
     command
         .current_dir(working_directory)
+        .kill_on_drop(true)
         .arg("--default-search")
         .arg("auto")
         .arg("--output")
@@ -69,7 +70,11 @@ pub async fn download_from_infojson(
     format: Option<&str>,
 ) -> Result<(), String> {
     let mut command = Command::new("yt-dlp");
-    command.current_dir(working_directory).arg("--load-info-json").arg(infojson_path);
+    command
+        .current_dir(working_directory)
+        .kill_on_drop(true)
+        .arg("--load-info-json")
+        .arg(infojson_path);
 
     if let Some(format) = format {
         command.arg("--format").arg(format);